@@ -0,0 +1,261 @@
+//! Minimal bencode decoder and encoder.
+//!
+//! Decoded values borrow from the input and remember the byte range they were decoded from, so the
+//! exact encoding of any value (such as the `info` dictionary of a torrent) can be hashed as is.
+
+use std::fmt;
+use std::ops::Range;
+
+/// Lists and dictionaries nested deeper than this are rejected.
+const MAX_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value<'a> {
+    Integer(i64),
+    Bytes(&'a [u8]),
+    List(Vec<Node<'a>>),
+    /// Entries in the order they were decoded. Sorted by key when encoded.
+    Dictionary(Vec<(&'a [u8], Node<'a>)>),
+}
+
+/// A value together with the byte range of its encoding in the decoded input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node<'a> {
+    pub value: Value<'a>,
+    pub range: Range<usize>,
+}
+
+impl<'a> Node<'a> {
+    /// Wraps a value that was not decoded from anything, for encoding.
+    pub fn new(value: Value<'a>) -> Node<'a> {
+        return Node { value, range: 0..0 }
+    }
+
+    /// Looks up `key` if this is a dictionary.
+    pub fn get(&self, key: &str) -> Option<&Node<'a>> {
+        let Value::Dictionary(entries) = &self.value else {
+            return None
+        };
+        return entries.iter().find(|(k, _)| *k == key.as_bytes()).map(|(_, node)| node)
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        let Value::Integer(integer) = self.value else {
+            return None
+        };
+        return Some(integer)
+    }
+
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        let Value::Bytes(bytes) = self.value else {
+            return None
+        };
+        return Some(bytes)
+    }
+
+    pub fn as_list(&self) -> Option<&[Node<'a>]> {
+        let Value::List(list) = &self.value else {
+            return None
+        };
+        return Some(list)
+    }
+
+    /// Encodes the value, ignoring `range`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        encode_value(&self.value, &mut output);
+        return output
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnexpectedEnd,
+    UnexpectedByte(u8),
+    InvalidInteger,
+    TooDeep,
+    TrailingData,
+    /// A dictionary lacks a required key.
+    MissingKey(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    /// Offset in the input where decoding failed.
+    pub position: usize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ErrorKind::UnexpectedByte(byte) => write!(f, "unexpected byte 0x{byte:02x}")?,
+            ErrorKind::InvalidInteger => write!(f, "invalid integer")?,
+            ErrorKind::TooDeep => write!(f, "nesting too deep")?,
+            ErrorKind::TrailingData => write!(f, "trailing data")?,
+            ErrorKind::MissingKey(key) => write!(f, "missing key {key:?}")?,
+        }
+        return write!(f, " at offset {}", self.position)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Decodes exactly one value spanning all of `bytes`.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::bencode::{decode, ErrorKind, Value};
+///
+/// let bytes = "d3:cow3:moo4:spaml1:a1:bee".as_bytes();
+/// let root = decode(bytes).unwrap();
+///
+/// assert_eq!(root.get("cow").unwrap().value, Value::Bytes("moo".as_bytes()));
+/// let spam = root.get("spam").unwrap();
+/// assert_eq!(&bytes[spam.range.clone()], "l1:a1:be".as_bytes());
+/// assert_eq!(root.encode(), bytes);
+///
+/// let error = decode("d-1:ai0ee".as_bytes()).unwrap_err();
+/// assert_eq!((error.kind, error.position), (ErrorKind::InvalidInteger, 1));
+/// assert_eq!(decode("-1:".as_bytes()).unwrap_err().kind, ErrorKind::InvalidInteger);
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<Node<'_>, Error> {
+    let mut decoder = Decoder { bytes, position: 0 };
+    let node = decoder.node(0)?;
+    if decoder.position != bytes.len() {
+        return Err(decoder.error(ErrorKind::TrailingData))
+    }
+    return Ok(node)
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn node(&mut self, depth: usize) -> Result<Node<'a>, Error> {
+        if depth > MAX_DEPTH {
+            return Err(self.error(ErrorKind::TooDeep))
+        }
+        let start = self.position;
+        let value = match self.peek()? {
+            b'i' => {
+                self.position += 1;
+                Value::Integer(self.integer(b'e')?)
+            }
+            b'l' => {
+                self.position += 1;
+                let mut list = Vec::new();
+                while self.peek()? != b'e' {
+                    list.push(self.node(depth + 1)?);
+                }
+                self.position += 1;
+                Value::List(list)
+            }
+            b'd' => {
+                self.position += 1;
+                let mut entries = Vec::new();
+                while self.peek()? != b'e' {
+                    let key = self.string()?;
+                    entries.push((key, self.node(depth + 1)?));
+                }
+                self.position += 1;
+                Value::Dictionary(entries)
+            }
+            b'-' | b'0'..=b'9' => Value::Bytes(self.string()?),
+            byte => return Err(self.error(ErrorKind::UnexpectedByte(byte))),
+        };
+        return Ok(Node { value, range: start..self.position })
+    }
+
+    fn string(&mut self) -> Result<&'a [u8], Error> {
+        let byte = self.peek()?;
+        // A string length is never signed, not even `-0`.
+        if byte == b'-' {
+            return Err(self.error(ErrorKind::InvalidInteger))
+        }
+        if !byte.is_ascii_digit() {
+            return Err(self.error(ErrorKind::UnexpectedByte(byte)))
+        }
+        let start = self.position;
+        let Ok(length) = usize::try_from(self.integer(b':')?) else {
+            return Err(Error { kind: ErrorKind::InvalidInteger, position: start })
+        };
+        if self.bytes.len() - self.position < length {
+            self.position = self.bytes.len();
+            return Err(self.error(ErrorKind::UnexpectedEnd))
+        }
+        let string = &self.bytes[self.position..self.position + length];
+        self.position += length;
+        return Ok(string)
+    }
+
+    /// Reads a canonical decimal integer up to `terminator`.
+    fn integer(&mut self, terminator: u8) -> Result<i64, Error> {
+        let start = self.position;
+        let end = match self.bytes[start..].iter().position(|&byte| byte == terminator) {
+            Some(offset) => start + offset,
+            None => {
+                self.position = self.bytes.len();
+                return Err(self.error(ErrorKind::UnexpectedEnd))
+            }
+        };
+        let digits = &self.bytes[start..end];
+        let canonical = match digits {
+            [b'0'] => true,
+            [b'-', b'1'..=b'9', rest @ ..] | [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+            _ => false,
+        };
+        let integer = std::str::from_utf8(digits).ok().filter(|_| canonical).and_then(|s| s.parse().ok());
+        let Some(integer) = integer else {
+            return Err(Error { kind: ErrorKind::InvalidInteger, position: start })
+        };
+        self.position = end + 1;
+        return Ok(integer)
+    }
+
+    fn peek(&self) -> Result<u8, Error> {
+        return self.bytes.get(self.position).copied().ok_or(self.error(ErrorKind::UnexpectedEnd))
+    }
+
+    fn error(&self, kind: ErrorKind) -> Error {
+        return Error { kind, position: self.position }
+    }
+}
+
+fn encode_value(value: &Value<'_>, output: &mut Vec<u8>) {
+    match value {
+        Value::Integer(integer) => {
+            output.push(b'i');
+            output.extend(integer.to_string().as_bytes());
+            output.push(b'e');
+        }
+        Value::Bytes(bytes) => encode_bytes(bytes, output),
+        Value::List(list) => {
+            output.push(b'l');
+            for node in list {
+                encode_value(&node.value, output);
+            }
+            output.push(b'e');
+        }
+        Value::Dictionary(entries) => {
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            output.push(b'd');
+            for (key, node) in entries {
+                encode_bytes(key, output);
+                encode_value(&node.value, output);
+            }
+            output.push(b'e');
+        }
+    }
+}
+
+fn encode_bytes(bytes: &[u8], output: &mut Vec<u8>) {
+    output.extend(bytes.len().to_string().as_bytes());
+    output.push(b':');
+    output.extend(bytes);
+}
@@ -0,0 +1,460 @@
+//! BitTorrent v1 piece hashing.
+//!
+//! A v1 torrent treats all of its files as one concatenated stream, cuts the stream into pieces of
+//! `piece length` bytes (only the last piece may be shorter) and stores the SHA1 of every piece, one
+//! 20-byte digest after another, in the `pieces` field of the info dictionary.
+//!
+//! Pieces are independent messages of the same length, so they are collected and handed to
+//! [`hash_many`](crate::hash_many) in batches.
+//!
+//! A torrent is identified by its infohash, the SHA1 of the bencoded `info` dictionary.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::bencode::{self, Node, Value};
+
+/// Number of pieces buffered before they are hashed together.
+const BATCH: usize = 8;
+
+/// Most bytes the buffer grows by for a single read.
+const READ_SIZE: usize = 1 << 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The piece length is zero.
+    ZeroPieceLength,
+    /// The `pieces` field does not hold one digest per piece of the torrent.
+    PieceCountMismatch,
+    /// A batch of pieces does not fit in memory.
+    PieceLengthTooLarge,
+    /// A length does not fit in a bencode integer.
+    LengthTooLarge,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::ZeroPieceLength => write!(f, "piece length is zero"),
+            Error::PieceCountMismatch => write!(f, "pieces does not hold one digest per piece"),
+            Error::PieceLengthTooLarge => write!(f, "piece length is too large"),
+            Error::LengthTooLarge => write!(f, "length does not fit in a bencode integer"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Computes the `pieces` field of a v1 torrent from a stream of file contents.
+///
+/// Files are fed in torrent order, a piece spans file boundaries the same way the concatenated
+/// stream does.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::bittorrent::PieceHasher;
+///
+/// let mut hasher = PieceHasher::new(4).unwrap();
+/// hasher.update("hello ".as_bytes());
+/// hasher.update("world".as_bytes());
+/// let pieces = hasher.finalize();
+///
+/// assert_eq!(pieces.len(), 3 * 20);
+/// assert_eq!(pieces[00..20], simd_sha1::hash("hell".as_bytes()));
+/// assert_eq!(pieces[20..40], simd_sha1::hash("o wo".as_bytes()));
+/// assert_eq!(pieces[40..60], simd_sha1::hash("rld".as_bytes()));
+/// ```
+pub struct PieceHasher {
+    piece_length: usize,
+    /// Bytes of `BATCH` pieces, the buffer is flushed when it holds this many.
+    capacity: usize,
+    buffer: Vec<u8>,
+    pieces: Vec<u8>,
+}
+
+impl PieceHasher {
+    /// Fails if `piece_length` is zero or a batch of pieces would not fit in memory.
+    ///
+    /// ```
+    /// use simd_sha1::bittorrent::{Error, PieceHasher};
+    ///
+    /// assert_eq!(PieceHasher::new(0).err(), Some(Error::ZeroPieceLength));
+    /// assert_eq!(PieceHasher::new(usize::MAX).err(), Some(Error::PieceLengthTooLarge));
+    /// ```
+    pub fn new(piece_length: usize) -> Result<PieceHasher, Error> {
+        if piece_length == 0 {
+            return Err(Error::ZeroPieceLength)
+        }
+        let Some(capacity) = piece_length.checked_mul(BATCH) else {
+            return Err(Error::PieceLengthTooLarge)
+        };
+        return Ok(PieceHasher { piece_length, capacity, buffer: Vec::new(), pieces: Vec::new() })
+    }
+
+    pub fn piece_length(&self) -> usize {
+        return self.piece_length
+    }
+
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let count = usize::min(self.capacity - self.buffer.len(), bytes.len());
+            self.buffer.extend_from_slice(&bytes[..count]);
+            bytes = &bytes[count..];
+            if self.buffer.len() == self.capacity {
+                self.flush();
+            }
+        }
+    }
+
+    /// Reads `reader` to the end and returns the number of bytes read.
+    pub fn update_reader<R: Read>(&mut self, mut reader: R) -> io::Result<u64> {
+        let mut total = 0;
+        loop {
+            let start = self.buffer.len();
+            self.buffer.resize(usize::min(self.capacity, start + READ_SIZE), 0);
+            let result = reader.read(&mut self.buffer[start..]);
+            let count = *result.as_ref().unwrap_or(&0);
+            self.buffer.truncate(start + count);
+            match result {
+                Ok(0) => return Ok(total),
+                Ok(_) => total += count as u64,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+            if self.buffer.len() == self.capacity {
+                self.flush();
+            }
+        }
+    }
+
+    /// Reads the file at `path` to the end and returns its length.
+    pub fn update_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<u64> {
+        return self.update_reader(File::open(path)?)
+    }
+
+    /// Returns the concatenated 20-byte digests of all pieces.
+    pub fn finalize(mut self) -> Vec<u8> {
+        self.flush();
+        if !self.buffer.is_empty() {
+            self.pieces.extend(crate::hash(&self.buffer));
+        }
+        return self.pieces
+    }
+
+    /// Hashes every complete piece in the buffer and keeps the rest.
+    fn flush(&mut self) {
+        let complete = self.buffer.len() - self.buffer.len() % self.piece_length;
+        let pieces: Vec<&[u8]> = self.buffer[..complete].chunks(self.piece_length).collect();
+        for digest in crate::hash_many(&pieces) {
+            self.pieces.extend(digest);
+        }
+        self.buffer.drain(..complete);
+    }
+}
+
+/// Computes the `pieces` field of a torrent made of the files at `paths`, in that order. A zero
+/// `piece_length` is reported as [`ErrorKind::InvalidInput`].
+pub fn hash_files<P: AsRef<Path>>(paths: &[P], piece_length: usize) -> io::Result<Vec<u8>> {
+    let mut hasher = PieceHasher::new(piece_length).map_err(|error| io::Error::new(ErrorKind::InvalidInput, error))?;
+    for path in paths {
+        hasher.update_file(path)?;
+    }
+    return Ok(hasher.finalize())
+}
+
+/// Checks downloaded data against the `pieces` field of a torrent.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::bittorrent::{PieceHasher, Verifier};
+///
+/// let data = "hello world".as_bytes();
+/// let mut hasher = PieceHasher::new(4).unwrap();
+/// hasher.update(data);
+/// let pieces = hasher.finalize();
+///
+/// let verifier = Verifier::new(&pieces, 4, data.len() as u64).unwrap();
+/// // the second piece is corrupted and the last one is not downloaded yet
+/// assert_eq!(verifier.verify("hellXo w".as_bytes()), [true, false, false]);
+/// assert!(verifier.verify_piece(2, "rld".as_bytes()));
+/// ```
+pub struct Verifier<'a> {
+    pieces: &'a [u8],
+    piece_length: usize,
+    length: u64,
+}
+
+impl<'a> Verifier<'a> {
+    /// `length` is the total length of all files in the torrent. All three usually come from an
+    /// untrusted `.torrent` file, so they are checked against each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simd_sha1::bittorrent::{Error, Verifier};
+    ///
+    /// assert_eq!(Verifier::new(&[0; 40], 0, 8).err(), Some(Error::ZeroPieceLength));
+    /// assert_eq!(Verifier::new(&[0; 40], 4, 9).err(), Some(Error::PieceCountMismatch));
+    /// assert_eq!(Verifier::new(&[0; 40], 1, u64::MAX).err(), Some(Error::PieceCountMismatch));
+    /// ```
+    pub fn new(pieces: &'a [u8], piece_length: usize, length: u64) -> Result<Verifier<'a>, Error> {
+        if piece_length == 0 {
+            return Err(Error::ZeroPieceLength)
+        }
+        let piece_count = length.div_ceil(piece_length as u64);
+        if piece_count.checked_mul(20) != Some(pieces.len() as u64) {
+            return Err(Error::PieceCountMismatch)
+        }
+        return Ok(Verifier { pieces, piece_length, length })
+    }
+
+    pub fn piece_count(&self) -> usize {
+        return self.pieces.len() / 20
+    }
+
+    /// Returns the byte range that piece `index` covers in the concatenated files.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn piece_range(&self, index: usize) -> Range<u64> {
+        assert!(index < self.piece_count(), "piece index out of range");
+        let start = index as u64 * self.piece_length as u64;
+        let end = u64::min(start + self.piece_length as u64, self.length);
+        return start..end
+    }
+
+    /// Checks a single piece. Returns `false` if `index` is out of range.
+    pub fn verify_piece(&self, index: usize, bytes: &[u8]) -> bool {
+        if index >= self.piece_count() {
+            return false
+        }
+        let range = self.piece_range(index);
+        return bytes.len() as u64 == range.end - range.start
+            && crate::hash(bytes)[..] == self.pieces[index * 20..index * 20 + 20]
+    }
+
+    /// Checks every piece of `data`, which holds the concatenated files from the start and may be
+    /// shorter than the torrent. Pieces that `data` does not cover completely are reported invalid.
+    pub fn verify(&self, data: &[u8]) -> Vec<bool> {
+        let mut pieces = Vec::new();
+        for index in 0..self.piece_count() {
+            let range = self.piece_range(index);
+            if range.end > data.len() as u64 {
+                break
+            }
+            pieces.push(&data[range.start as usize..range.end as usize]);
+        }
+
+        let mut valid = vec![false; self.piece_count()];
+        for (index, digest) in crate::hash_many(&pieces).into_iter().enumerate() {
+            valid[index] = digest[..] == self.pieces[index * 20..index * 20 + 20];
+        }
+        return valid
+    }
+}
+
+/// Returns the infohash of a `.torrent` file, the SHA1 of its `info` dictionary exactly as encoded
+/// in `torrent`.
+///
+/// # Examples
+///
+/// ```
+/// let torrent = "d8:announce3:url4:infod6:lengthi5e4:name1:a12:piece lengthi4e6:pieces0:ee".as_bytes();
+///
+/// let infohash = simd_sha1::bittorrent::infohash(torrent).unwrap();
+///
+/// assert_eq!(infohash, simd_sha1::hash("d6:lengthi5e4:name1:a12:piece lengthi4e6:pieces0:e".as_bytes()));
+///
+/// let error = simd_sha1::bittorrent::infohash("d8:announce3:urle".as_bytes()).unwrap_err();
+/// assert_eq!(error.position, 16);
+/// ```
+pub fn infohash(torrent: &[u8]) -> Result<[u8; 20], bencode::Error> {
+    let root = bencode::decode(torrent)?;
+    let Some(info) = root.get("info") else {
+        // The key would have been before the closing `e` of the dictionary.
+        let position = match root.value {
+            Value::Dictionary(_) => root.range.end - 1,
+            _ => root.range.start,
+        };
+        return Err(bencode::Error { kind: bencode::ErrorKind::MissingKey("info"), position })
+    };
+    return Ok(crate::hash(&torrent[info.range.clone()]))
+}
+
+/// A file of a multi-file torrent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEntry {
+    /// Path components below the torrent directory.
+    pub path: Vec<String>,
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Files {
+    Single { length: u64 },
+    Multiple(Vec<FileEntry>),
+}
+
+/// The contents of a v1 `.torrent` file.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::bittorrent::{Files, Metainfo, PieceHasher};
+///
+/// let mut hasher = PieceHasher::new(16384).unwrap();
+/// hasher.update("hello world".as_bytes());
+///
+/// let metainfo = Metainfo {
+///     announce: Some("http://tracker.example/announce".to_owned()),
+///     name: "hello.txt".to_owned(),
+///     piece_length: hasher.piece_length(),
+///     pieces: hasher.finalize(),
+///     files: Files::Single { length: 11 },
+/// };
+/// let torrent = metainfo.encode().unwrap();
+///
+/// assert_eq!(simd_sha1::bittorrent::infohash(&torrent).unwrap(), metainfo.infohash().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metainfo {
+    pub announce: Option<String>,
+    pub name: String,
+    pub piece_length: usize,
+    /// Concatenated piece digests, as returned by [`PieceHasher::finalize`].
+    pub pieces: Vec<u8>,
+    pub files: Files,
+}
+
+impl Metainfo {
+    /// Encodes the `info` dictionary.
+    ///
+    /// Fails with [`Error::LengthTooLarge`] if the piece length or a file length exceeds `i64::MAX`.
+    ///
+    /// ```
+    /// use simd_sha1::bittorrent::{Error, Files, Metainfo};
+    ///
+    /// let metainfo = Metainfo {
+    ///     announce: None,
+    ///     name: "huge".to_owned(),
+    ///     piece_length: 16384,
+    ///     pieces: Vec::new(),
+    ///     files: Files::Single { length: u64::MAX },
+    /// };
+    ///
+    /// assert_eq!(metainfo.encode_info(), Err(Error::LengthTooLarge));
+    /// ```
+    pub fn encode_info(&self) -> Result<Vec<u8>, Error> {
+        return Ok(self.info()?.encode())
+    }
+
+    /// Encodes the whole `.torrent` file.
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let mut entries = vec![("info".as_bytes(), self.info()?)];
+        if let Some(announce) = &self.announce {
+            entries.push(("announce".as_bytes(), Node::new(Value::Bytes(announce.as_bytes()))));
+        }
+        return Ok(Node::new(Value::Dictionary(entries)).encode())
+    }
+
+    pub fn infohash(&self) -> Result<[u8; 20], Error> {
+        return Ok(crate::hash(&self.encode_info()?))
+    }
+
+    fn info(&self) -> Result<Node<'_>, Error> {
+        let mut entries = vec![
+            ("name".as_bytes(), Node::new(Value::Bytes(self.name.as_bytes()))),
+            ("piece length".as_bytes(), integer(self.piece_length)?),
+            ("pieces".as_bytes(), Node::new(Value::Bytes(&self.pieces))),
+        ];
+        match &self.files {
+            Files::Single { length } => {
+                entries.push(("length".as_bytes(), integer(*length)?));
+            }
+            Files::Multiple(files) => {
+                let files = files.iter().map(|file| {
+                    let path = file.path.iter().map(|component| Node::new(Value::Bytes(component.as_bytes())));
+                    return Ok(Node::new(Value::Dictionary(vec![
+                        ("length".as_bytes(), integer(file.length)?),
+                        ("path".as_bytes(), Node::new(Value::List(path.collect()))),
+                    ])))
+                });
+                entries.push(("files".as_bytes(), Node::new(Value::List(files.collect::<Result<_, Error>>()?))));
+            }
+        }
+        return Ok(Node::new(Value::Dictionary(entries)))
+    }
+}
+
+/// Wraps a length as a bencode integer.
+fn integer<T: TryInto<i64>>(length: T) -> Result<Node<'static>, Error> {
+    let Ok(integer) = length.try_into() else {
+        return Err(Error::LengthTooLarge)
+    };
+    return Ok(Node::new(Value::Integer(integer)))
+}
+
+/// Returns a magnet link with the infohash in hex, optionally with a display name.
+///
+/// # Examples
+///
+/// ```
+/// let infohash = [0xAB; 20];
+///
+/// assert_eq!(
+///     simd_sha1::bittorrent::magnet_link(&infohash, Some("a b")),
+///     "magnet:?xt=urn:btih:abababababababababababababababababababab&dn=a%20b"
+/// );
+/// ```
+pub fn magnet_link(infohash: &[u8; 20], name: Option<&str>) -> String {
+    let mut link = String::from("magnet:?xt=urn:btih:");
+    for byte in infohash {
+        link.push_str(&format!("{byte:02x}"));
+    }
+    push_name(&mut link, name);
+    return link
+}
+
+/// Returns a magnet link with the infohash in Base32 (RFC 4648), optionally with a display name.
+///
+/// # Examples
+///
+/// ```
+/// let infohash = [0xAB; 20];
+///
+/// assert_eq!(
+///     simd_sha1::bittorrent::magnet_link_base32(&infohash, None),
+///     "magnet:?xt=urn:btih:VOV2XK5LVOV2XK5LVOV2XK5LVOV2XK5L"
+/// );
+/// ```
+pub fn magnet_link_base32(infohash: &[u8; 20], name: Option<&str>) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut link = String::from("magnet:?xt=urn:btih:");
+    for group in infohash.chunks(5) {
+        let bits = group.iter().fold(0u64, |bits, &byte| bits << 8 | byte as u64);
+        for shift in (0..8).rev() {
+            link.push(ALPHABET[(bits >> (shift * 5) & 31) as usize] as char);
+        }
+    }
+    push_name(&mut link, name);
+    return link
+}
+
+fn push_name(link: &mut String, name: Option<&str>) {
+    let Some(name) = name else {
+        return
+    };
+    link.push_str("&dn=");
+    for &byte in name.as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => link.push(byte as char),
+            _ => link.push_str(&format!("%{byte:02X}")),
+        }
+    }
+}
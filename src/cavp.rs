@@ -0,0 +1,634 @@
+//! NIST CAVP conformance testing in the SHAVS response format.
+//!
+//! A `.rsp` file holds byte- or bit-oriented `Len`/`Msg`/`MD` records (`SHA1ShortMsg.rsp`,
+//! `SHA1LongMsg.rsp` and their bit-oriented counterparts) or a Monte Carlo test, a `Seed` followed by
+//! `COUNT`/`MD` checkpoints (`SHA1Monte.rsp`). [`parse`] reads either, [`run`] checks every vector
+//! against every [`Backend`] and returns a [`Report`] that renders as JSON.
+//!
+//! The DRBG response files (`Hash_DRBG.rsp`, `HMAC_DRBG.rsp`) are read by [`parse_drbg`] and run
+//! against [`drbg`](crate::drbg) by [`run_drbg`].
+//!
+//! The byte-oriented SHAVS files for SHA-1 are embedded unchanged as [`SHORT_MSG`], [`LONG_MSG`]
+//! and [`MONTE`], and the `[SHA-1]` sections of the three HMAC_DRBG files as
+//! [`HMAC_DRBG_NO_RESEED`], [`HMAC_DRBG_PR_FALSE`] and [`HMAC_DRBG_PR_TRUE`]. The Hash_DRBG files
+//! are not embedded; they and other files from the CAVP archive, such as the bit-oriented ones, run
+//! the same way.
+
+use std::fmt;
+
+use crate::drbg::{self, HashDrbg, HmacDrbg};
+use crate::Sha1;
+
+/// `SHA1ShortMsg.rsp` from the CAVP SHAVS archive (CAVS 11.0): messages of 0 to 512 bits.
+pub const SHORT_MSG: &str = include_str!("../vectors/SHA1ShortMsg.rsp");
+
+/// `SHA1LongMsg.rsp` from the CAVP SHAVS archive (CAVS 11.0): messages of 1304 to 51200 bits.
+pub const LONG_MSG: &str = include_str!("../vectors/SHA1LongMsg.rsp");
+
+/// `SHA1Monte.rsp` from the CAVP SHAVS archive (CAVS 11.1): the Monte Carlo test, 100 checkpoints.
+pub const MONTE: &str = include_str!("../vectors/SHA1Monte.rsp");
+
+/// `[SHA-1]` sections of `drbgvectors_no_reseed/HMAC_DRBG.rsp` (CAVS 14.3): two requests after
+/// instantiation.
+pub const HMAC_DRBG_NO_RESEED: &str = include_str!("../vectors/HMAC_DRBG_no_reseed.rsp");
+
+/// `[SHA-1]` sections of `drbgvectors_pr_false/HMAC_DRBG.rsp` (CAVS 14.3): a reseed, then two requests.
+pub const HMAC_DRBG_PR_FALSE: &str = include_str!("../vectors/HMAC_DRBG_pr_false.rsp");
+
+/// `[SHA-1]` sections of `drbgvectors_pr_true/HMAC_DRBG.rsp` (CAVS 14.3): two requests with
+/// prediction resistance.
+pub const HMAC_DRBG_PR_TRUE: &str = include_str!("../vectors/HMAC_DRBG_pr_true.rsp");
+
+/// Number of hashes between two Monte Carlo checkpoints.
+const MONTE_ITERATIONS: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vector {
+    /// The first `len` bits of `msg` hash to `md`.
+    Message { line: usize, len: u64, msg: Vec<u8>, md: [u8; 20] },
+    /// Chained hashing from `seed` reaches each checkpoint in turn.
+    Monte { line: usize, seed: [u8; 20], checkpoints: Vec<[u8; 20]> },
+}
+
+impl Vector {
+    /// Line of the `Len` or `Seed` record, counting from 1.
+    pub fn line(&self) -> usize {
+        return match self {
+            Vector::Message { line, .. } | Vector::Monte { line, .. } => *line,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The line is not a comment, a `[...]` header or `key = value`.
+    Syntax,
+    UnknownKey,
+    InvalidNumber,
+    InvalidHex,
+    /// `Msg` has fewer bits than `Len`.
+    ShortMessage,
+    /// The record is out of order, e.g. `MD` before `Msg`.
+    Unexpected(&'static str),
+    /// The file ends in the middle of a record.
+    Missing(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    /// Line of the input where parsing failed, counting from 1.
+    pub line: usize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::Syntax => write!(f, "expected `key = value`")?,
+            ErrorKind::UnknownKey => write!(f, "unknown key")?,
+            ErrorKind::InvalidNumber => write!(f, "invalid number")?,
+            ErrorKind::InvalidHex => write!(f, "invalid hex")?,
+            ErrorKind::ShortMessage => write!(f, "message shorter than its length")?,
+            ErrorKind::Unexpected(key) => write!(f, "unexpected {key}")?,
+            ErrorKind::Missing(key) => write!(f, "missing {key}")?,
+        }
+        return write!(f, " at line {}", self.line)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses the vectors of a `.rsp` file. Comments and `[L = 20]` headers are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::cavp::{self, Vector};
+///
+/// let vectors = cavp::parse(cavp::SHORT_MSG).unwrap();
+///
+/// let Vector::Message { len, msg, md, .. } = &vectors[0] else { panic!() };
+/// assert_eq!(vectors.len(), 65);
+/// assert_eq!((*len, msg.len()), (0, 0));
+/// assert_eq!(*md, simd_sha1::hash(&[]));
+/// ```
+pub fn parse(text: &str) -> Result<Vec<Vector>, Error> {
+    let mut vectors = Vec::new();
+    // `Len` and `Msg` of the record being read
+    let mut pending: Option<(usize, u64, Option<Vec<u8>>)> = None;
+    // whether the last vector is a Monte Carlo test still taking checkpoints, and the `COUNT` read
+    let mut monte = false;
+    let mut count: Option<usize> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let error = |kind| Error { kind, line: number };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue
+        }
+        let (key, value) = line.split_once('=').ok_or(error(ErrorKind::Syntax))?;
+        let value = value.trim();
+        match key.trim() {
+            "Len" => {
+                if pending.is_some() {
+                    return Err(error(ErrorKind::Unexpected("Len")))
+                }
+                let len = value.parse().map_err(|_| error(ErrorKind::InvalidNumber))?;
+                pending = Some((number, len, None));
+                monte = false;
+            }
+            "Msg" => {
+                let Some((_, len, msg @ None)) = &mut pending else {
+                    return Err(error(ErrorKind::Unexpected("Msg")))
+                };
+                let mut bytes = decode_hex(value).ok_or(error(ErrorKind::InvalidHex))?;
+                let byte_len = len.div_ceil(8) as usize;
+                if bytes.len() < byte_len {
+                    return Err(error(ErrorKind::ShortMessage))
+                }
+                // a zero-length message is written as `Msg = 00`
+                bytes.truncate(byte_len);
+                *msg = Some(bytes);
+            }
+            "MD" => {
+                let md = decode_digest(value).ok_or(error(ErrorKind::InvalidHex))?;
+                match pending.take() {
+                    Some((line, len, Some(msg))) => vectors.push(Vector::Message { line, len, msg, md }),
+                    Some(_) => return Err(error(ErrorKind::Unexpected("MD"))),
+                    None => {
+                        let Some(Vector::Monte { checkpoints, .. }) = vectors.last_mut().filter(|_| monte) else {
+                            return Err(error(ErrorKind::Unexpected("MD")))
+                        };
+                        if count.take() != Some(checkpoints.len()) {
+                            return Err(error(ErrorKind::Missing("COUNT")))
+                        }
+                        checkpoints.push(md);
+                    }
+                }
+            }
+            "Seed" => {
+                if pending.is_some() {
+                    return Err(error(ErrorKind::Unexpected("Seed")))
+                }
+                let seed = decode_digest(value).ok_or(error(ErrorKind::InvalidHex))?;
+                vectors.push(Vector::Monte { line: number, seed, checkpoints: Vec::new() });
+                monte = true;
+                count = None;
+            }
+            "COUNT" => {
+                if !monte || count.is_some() {
+                    return Err(error(ErrorKind::Unexpected("COUNT")))
+                }
+                count = Some(value.parse().map_err(|_| error(ErrorKind::InvalidNumber))?);
+            }
+            _ => return Err(error(ErrorKind::UnknownKey)),
+        }
+    }
+
+    let end = text.lines().count();
+    if pending.is_some() || count.is_some() {
+        return Err(Error { kind: ErrorKind::Missing("MD"), line: end })
+    }
+    return Ok(vectors)
+}
+
+/// An implementation a vector is run on.
+///
+/// The selected SIMD backend is fixed at compile time (or picked at startup with `arm-detect`). It is
+/// reached through each public entry point, which runs a different part of it: the single-message
+/// kernel, the multi-buffer kernel, and the bare compression function behind [`Sha1`]. The other
+/// backends compiled alongside it are run directly: the portable scalar one everywhere, SSE behind
+/// SHA-NI or AVX2, and NEON behind the ARM SHA1 instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// [`hash`](crate::hash) of the selected backend, byte-oriented messages only.
+    Hash,
+    /// [`hash_many`](crate::hash_many) of the selected backend, byte-oriented messages only.
+    HashMany,
+    /// [`par_hash_many`](crate::par_hash_many), byte-oriented messages only.
+    #[cfg(feature = "parallel")]
+    ParHashMany,
+    /// [`Sha1`] over the selected backend, with [`update_bits`](Sha1::update_bits) for bit-oriented
+    /// messages.
+    Streaming,
+    /// The portable scalar backend, byte-oriented messages only.
+    Scalar,
+    /// The SSE backend, byte-oriented messages only.
+    #[cfg(all(target_feature = "sse2", any(target_feature = "sha", target_feature = "avx2")))]
+    Sse,
+    /// The NEON backend, byte-oriented messages only.
+    #[cfg(all(target_feature = "sha2", target_feature = "neon"))]
+    Neon,
+}
+
+impl Backend {
+    /// Every backend built into this crate.
+    pub const ALL: &'static [Backend] = &[
+        Backend::Hash,
+        Backend::HashMany,
+        #[cfg(feature = "parallel")]
+        Backend::ParHashMany,
+        Backend::Streaming,
+        Backend::Scalar,
+        #[cfg(all(target_feature = "sse2", any(target_feature = "sha", target_feature = "avx2")))]
+        Backend::Sse,
+        #[cfg(all(target_feature = "sha2", target_feature = "neon"))]
+        Backend::Neon,
+    ];
+
+    pub fn name(self) -> &'static str {
+        return match self {
+            Backend::Hash => "hash",
+            Backend::HashMany => "hash_many",
+            #[cfg(feature = "parallel")]
+            Backend::ParHashMany => "par_hash_many",
+            Backend::Streaming => "streaming",
+            Backend::Scalar => "scalar",
+            #[cfg(all(target_feature = "sse2", any(target_feature = "sha", target_feature = "avx2")))]
+            Backend::Sse => "sse",
+            #[cfg(all(target_feature = "sha2", target_feature = "neon"))]
+            Backend::Neon => "neon",
+        }
+    }
+
+    /// Hashes the first `bit_len` bits of `bytes`, or returns `None` if this backend only takes
+    /// whole bytes.
+    fn hash_bits(self, bytes: &[u8], bit_len: u64) -> Option<[u8; 20]> {
+        if self != Backend::Streaming && !bit_len.is_multiple_of(8) {
+            return None
+        }
+        return Some(self.hash_parts(&[bytes], bit_len))
+    }
+
+    /// Hashes the concatenation of `parts`, of `bit_len` bits in total.
+    fn hash_parts(self, parts: &[&[u8]], bit_len: u64) -> [u8; 20] {
+        let joined = || parts.concat();
+        return match self {
+            Backend::Hash => crate::hash(&joined()),
+            Backend::HashMany => crate::hash_many(&[&joined()])[0],
+            #[cfg(feature = "parallel")]
+            Backend::ParHashMany => crate::par_hash_many(&[&joined()])[0],
+            Backend::Scalar => crate::sha1_scalar::hash(&joined()),
+            #[cfg(all(target_feature = "sse2", any(target_feature = "sha", target_feature = "avx2")))]
+            Backend::Sse => crate::sha1_sse::hash(&joined()),
+            #[cfg(all(target_feature = "sha2", target_feature = "neon"))]
+            Backend::Neon => crate::sha1_neon::hash(&joined()),
+            Backend::Streaming => {
+                let mut hasher = Sha1::new();
+                let (last, whole) = parts.split_last().unwrap();
+                let mut remaining = bit_len;
+                for part in whole {
+                    hasher.update(part);
+                    remaining -= part.len() as u64 * 8;
+                }
+                hasher.update_bits(last, remaining);
+                hasher.finalize()
+            }
+        }
+    }
+}
+
+/// A wrong digest or DRBG output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    /// The Monte Carlo checkpoint that was missed; later ones are not checked.
+    pub count: Option<usize>,
+    pub expected: Vec<u8>,
+    /// Empty if the DRBG refused the request.
+    pub actual: Vec<u8>,
+}
+
+/// The result of one vector on one backend.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// Line of the `Len`, `Seed` or, for DRBGs, `COUNT` record.
+    pub line: usize,
+    pub backend: Backend,
+    pub failure: Option<Failure>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// One outcome per vector and backend, in file order. Bit-oriented messages are run on
+    /// [`Backend::Streaming`] only.
+    pub outcomes: Vec<Outcome>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        return self.outcomes.iter().all(|outcome| outcome.failure.is_none())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &Outcome> {
+        return self.outcomes.iter().filter(|outcome| outcome.failure.is_some())
+    }
+
+    /// Renders the report as a JSON object: the crate version, the target architecture, a summary
+    /// and every outcome.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simd_sha1::cavp;
+    ///
+    /// let report = cavp::run(&cavp::parse(cavp::SHORT_MSG).unwrap());
+    /// let json = report.to_json();
+    ///
+    /// assert!(json.contains(r#""passed":true"#));
+    /// assert!(json.contains(r#"{"line":8,"backend":"hash","passed":true}"#));
+    /// assert!(json.contains(r#"{"line":8,"backend":"scalar","passed":true}"#));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let backends: Vec<String> = Backend::ALL.iter().map(|backend| format!("\"{}\"", backend.name())).collect();
+        let mut json = format!(
+            r#"{{"implementation":"simd-sha1 {}","arch":"{}","backends":[{}],"passed":{},"checks":{},"failures":{},"outcomes":["#,
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::ARCH,
+            backends.join(","),
+            self.passed(),
+            self.outcomes.len(),
+            self.failures().count(),
+        );
+        for (index, outcome) in self.outcomes.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json += &format!(r#"{{"line":{},"backend":"{}","passed":{}"#, outcome.line, outcome.backend.name(), outcome.failure.is_none());
+            if let Some(failure) = &outcome.failure {
+                if let Some(count) = failure.count {
+                    json += &format!(r#","count":{count}"#);
+                }
+                json += &format!(r#","expected":"{}","actual":"{}""#, encode_hex(&failure.expected), encode_hex(&failure.actual));
+            }
+            json.push('}');
+        }
+        json += "]}";
+        return json
+    }
+}
+
+/// Runs every vector on every backend.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::cavp;
+///
+/// let mut vectors = cavp::parse(cavp::MONTE).unwrap();
+/// // the first 3 of 100 checkpoints
+/// let cavp::Vector::Monte { checkpoints, .. } = &mut vectors[0] else { panic!() };
+/// checkpoints.truncate(3);
+///
+/// let report = cavp::run(&vectors);
+///
+/// assert!(report.passed());
+/// assert_eq!(report.outcomes.len(), cavp::Backend::ALL.len());
+/// ```
+pub fn run(vectors: &[Vector]) -> Report {
+    let mut report = Report::default();
+    for vector in vectors {
+        for &backend in Backend::ALL {
+            let failure = match vector {
+                Vector::Message { len, msg, md, .. } => {
+                    let Some(actual) = backend.hash_bits(msg, *len) else { continue };
+                    (actual != *md).then(|| Failure { count: None, expected: md.to_vec(), actual: actual.to_vec() })
+                }
+                Vector::Monte { seed, checkpoints, .. } => monte(backend, *seed, checkpoints),
+            };
+            report.outcomes.push(Outcome { line: vector.line(), backend, failure });
+        }
+    }
+    return report
+}
+
+/// Runs [`SHORT_MSG`], [`LONG_MSG`], [`MONTE`] and the HMAC_DRBG files.
+pub fn run_embedded() -> Report {
+    let mut report = run(&parse(SHORT_MSG).unwrap());
+    report.outcomes.extend(run(&parse(LONG_MSG).unwrap()).outcomes);
+    report.outcomes.extend(run(&parse(MONTE).unwrap()).outcomes);
+    for text in [HMAC_DRBG_NO_RESEED, HMAC_DRBG_PR_FALSE, HMAC_DRBG_PR_TRUE] {
+        report.outcomes.extend(run_drbg(Mechanism::HmacDrbg, &parse_drbg(text).unwrap()).outcomes);
+    }
+    return report
+}
+
+/// The SHAVS Monte Carlo procedure: each digest is the hash of the three before it, the first three
+/// being the seed, and every 1000th digest is a checkpoint and the next seed.
+fn monte(backend: Backend, mut seed: [u8; 20], checkpoints: &[[u8; 20]]) -> Option<Failure> {
+    for (count, &expected) in checkpoints.iter().enumerate() {
+        let mut md = [seed; 3];
+        for _ in 0..MONTE_ITERATIONS {
+            let next = backend.hash_parts(&[&md[0], &md[1], &md[2]], 60 * 8);
+            md = [md[1], md[2], next];
+        }
+        seed = md[2];
+        if seed != expected {
+            return Some(Failure { count: Some(count), expected: expected.to_vec(), actual: seed.to_vec() })
+        }
+    }
+    return None
+}
+
+/// The DRBG a response file is for; CAVP has one file per mechanism.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mechanism {
+    HashDrbg,
+    HmacDrbg,
+}
+
+/// A `COUNT` record of a DRBG response file: instantiate, optionally reseed, generate once per
+/// additional input, and compare the output of the last request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrbgVector {
+    /// Line of the `COUNT` record.
+    pub line: usize,
+    pub prediction_resistance: bool,
+    pub entropy_input: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub personalization: Vec<u8>,
+    /// `EntropyInputReseed` and `AdditionalInputReseed`.
+    pub reseed: Option<(Vec<u8>, Vec<u8>)>,
+    /// `AdditionalInput` of each request, with its `EntropyInputPR` under prediction resistance.
+    pub generate: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+    pub returned_bits: Vec<u8>,
+}
+
+/// Parses the `[SHA-1]` sections of a CAVP DRBG response file; sections for other hash functions are
+/// skipped.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::cavp::{self, Mechanism};
+///
+/// let vectors = cavp::parse_drbg(cavp::HMAC_DRBG_PR_TRUE).unwrap();
+/// let report = cavp::run_drbg(Mechanism::HmacDrbg, &vectors);
+///
+/// assert_eq!(vectors.len(), 60);
+/// assert!(vectors.iter().all(|vector| vector.prediction_resistance && vector.generate.len() == 2));
+/// assert!(report.passed());
+///
+/// let vectors = cavp::parse_drbg(cavp::HMAC_DRBG_PR_FALSE).unwrap();
+/// assert!(vectors.iter().all(|vector| vector.reseed.is_some()));
+/// assert!(cavp::run_drbg(Mechanism::HmacDrbg, &vectors).passed());
+///
+/// assert!(cavp::run_drbg(Mechanism::HmacDrbg, &cavp::parse_drbg(cavp::HMAC_DRBG_NO_RESEED).unwrap()).passed());
+/// ```
+pub fn parse_drbg(text: &str) -> Result<Vec<DrbgVector>, Error> {
+    let mut vectors = Vec::new();
+    let mut sha1 = false;
+    let mut prediction_resistance = false;
+    let mut current: Option<DrbgVector> = None;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let error = |kind| Error { kind, line: number };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+        if let Some(header) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            match header.split_once('=') {
+                Some((key, value)) if key.trim() == "PredictionResistance" => prediction_resistance = value.trim() == "True",
+                Some(_) => {}
+                None => sha1 = header == "SHA-1",
+            }
+            continue
+        }
+        if !sha1 {
+            continue
+        }
+
+        let (key, value) = line.split_once('=').ok_or(error(ErrorKind::Syntax))?;
+        let key = key.trim();
+        if key == "COUNT" {
+            if current.is_some() {
+                return Err(error(ErrorKind::Missing("ReturnedBits")))
+            }
+            current = Some(DrbgVector {
+                line: number,
+                prediction_resistance,
+                entropy_input: Vec::new(),
+                nonce: Vec::new(),
+                personalization: Vec::new(),
+                reseed: None,
+                generate: Vec::new(),
+                returned_bits: Vec::new(),
+            });
+            continue
+        }
+
+        let Some(vector) = &mut current else {
+            return Err(error(ErrorKind::Missing("COUNT")))
+        };
+        let bytes = decode_hex(value.trim()).ok_or(error(ErrorKind::InvalidHex))?;
+        match key {
+            "EntropyInput" => vector.entropy_input = bytes,
+            "Nonce" => vector.nonce = bytes,
+            "PersonalizationString" => vector.personalization = bytes,
+            "EntropyInputReseed" => vector.reseed = Some((bytes, Vec::new())),
+            "AdditionalInputReseed" => {
+                let Some((_, additional_input)) = &mut vector.reseed else {
+                    return Err(error(ErrorKind::Unexpected("AdditionalInputReseed")))
+                };
+                *additional_input = bytes;
+            }
+            "AdditionalInput" => vector.generate.push((bytes, None)),
+            "EntropyInputPR" => {
+                let Some((_, entropy_input @ None)) = vector.generate.last_mut() else {
+                    return Err(error(ErrorKind::Unexpected("EntropyInputPR")))
+                };
+                *entropy_input = Some(bytes);
+            }
+            "ReturnedBits" => {
+                vector.returned_bits = bytes;
+                vectors.extend(current.take());
+            }
+            _ => return Err(error(ErrorKind::UnknownKey)),
+        }
+    }
+
+    if current.is_some() {
+        return Err(Error { kind: ErrorKind::Missing("ReturnedBits"), line: text.lines().count() })
+    }
+    return Ok(vectors)
+}
+
+/// Runs every DRBG vector. The DRBGs hash through [`Sha1`] and the HMAC midstates, so outcomes are
+/// reported for [`Backend::Streaming`].
+pub fn run_drbg(mechanism: Mechanism, vectors: &[DrbgVector]) -> Report {
+    let mut report = Report::default();
+    for vector in vectors {
+        let output = match mechanism {
+            Mechanism::HashDrbg => drbg_output::<HashDrbg>(vector),
+            Mechanism::HmacDrbg => drbg_output::<HmacDrbg>(vector),
+        };
+        let actual = output.unwrap_or_default();
+        let failure = (actual != vector.returned_bits).then(|| Failure { count: None, expected: vector.returned_bits.clone(), actual });
+        report.outcomes.push(Outcome { line: vector.line, backend: Backend::Streaming, failure });
+    }
+    return report
+}
+
+fn drbg_output<D: Drbg>(vector: &DrbgVector) -> Result<Vec<u8>, drbg::Error> {
+    let mut drbg = D::instantiate(&vector.entropy_input, &vector.nonce, &vector.personalization, vector.prediction_resistance)?;
+    if let Some((entropy_input, additional_input)) = &vector.reseed {
+        drbg.reseed(entropy_input, additional_input)?;
+    }
+    let mut output = vec![0; vector.returned_bits.len()];
+    for (additional_input, entropy_input) in &vector.generate {
+        drbg.generate(&mut output, additional_input, entropy_input.as_deref())?;
+    }
+    return Ok(output)
+}
+
+/// The operations shared by [`HashDrbg`] and [`HmacDrbg`].
+trait Drbg: Sized {
+    fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8], prediction_resistance: bool) -> Result<Self, drbg::Error>;
+    fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), drbg::Error>;
+    fn generate(&mut self, output: &mut [u8], additional_input: &[u8], prediction_resistance: Option<&[u8]>) -> Result<(), drbg::Error>;
+}
+
+impl Drbg for HashDrbg {
+    fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8], prediction_resistance: bool) -> Result<Self, drbg::Error> {
+        return HashDrbg::instantiate(entropy_input, nonce, personalization, prediction_resistance)
+    }
+
+    fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), drbg::Error> {
+        return HashDrbg::reseed(self, entropy_input, additional_input)
+    }
+
+    fn generate(&mut self, output: &mut [u8], additional_input: &[u8], prediction_resistance: Option<&[u8]>) -> Result<(), drbg::Error> {
+        return HashDrbg::generate(self, output, additional_input, prediction_resistance)
+    }
+}
+
+impl Drbg for HmacDrbg {
+    fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8], prediction_resistance: bool) -> Result<Self, drbg::Error> {
+        return HmacDrbg::instantiate(entropy_input, nonce, personalization, prediction_resistance)
+    }
+
+    fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), drbg::Error> {
+        return HmacDrbg::reseed(self, entropy_input, additional_input)
+    }
+
+    fn generate(&mut self, output: &mut [u8], additional_input: &[u8], prediction_resistance: Option<&[u8]>) -> Result<(), drbg::Error> {
+        return HmacDrbg::generate(self, output, additional_input, prediction_resistance)
+    }
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None
+    }
+    return (0..text.len()).step_by(2).map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok()).collect()
+}
+
+fn decode_digest(text: &str) -> Option<[u8; 20]> {
+    return decode_hex(text)?.try_into().ok()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    return bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
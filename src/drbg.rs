@@ -0,0 +1,280 @@
+//! Hash_DRBG and HMAC_DRBG with SHA1 (NIST SP 800-90A).
+//!
+//! Both are deterministic: entropy and nonces are passed in by the caller, which is what a test
+//! harness reproducing another implementation needs. Inputs of [`instantiate`](HashDrbg::instantiate),
+//! [`reseed`](HashDrbg::reseed) and [`generate`](HashDrbg::generate) follow the standard; an empty
+//! additional input or personalization string is the same as none.
+//!
+//! With SHA1 the security strength is 128 bits, so entropy inputs must be at least 16 bytes. A
+//! request produces at most 2^16 bytes, and 2^48 requests may be made between two reseeds.
+
+use std::fmt;
+
+use crate::hmac::Key;
+use crate::Sha1;
+
+/// `seedlen` of Hash_DRBG with SHA1, in bytes.
+const SEED_LEN: usize = 55;
+
+const MIN_ENTROPY: usize = 16;
+const MAX_REQUEST: usize = 1 << 16;
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The entropy input is shorter than the security strength.
+    InsufficientEntropy,
+    /// The reseed interval is exhausted.
+    ReseedRequired,
+    /// Prediction resistance was requested from an instance created without it.
+    PredictionResistanceUnsupported,
+    /// More than 2^16 bytes were requested at once.
+    RequestTooLarge,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::InsufficientEntropy => write!(f, "entropy input shorter than {MIN_ENTROPY} bytes"),
+            Error::ReseedRequired => write!(f, "reseed required"),
+            Error::PredictionResistanceUnsupported => write!(f, "instantiated without prediction resistance"),
+            Error::RequestTooLarge => write!(f, "more than {MAX_REQUEST} bytes requested"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Hash_DRBG (SP 800-90A section 10.1.1).
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::drbg::HashDrbg;
+///
+/// let entropy = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+/// let nonce = [0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27];
+/// let mut drbg = HashDrbg::instantiate(&entropy, &nonce, &[], false).unwrap();
+/// drbg.reseed(&entropy.map(|byte| byte | 0x80), &[]).unwrap();
+///
+/// let mut output = [0; 80];
+/// drbg.generate(&mut output, &[], None).unwrap();
+/// drbg.generate(&mut output, &[], None).unwrap();
+///
+/// assert_eq!(output[..20], [
+///     0x7F, 0x84, 0x90, 0x5E, 0xCF, 0xBE, 0xDB, 0x90, 0xBB, 0x88,
+///     0xF8, 0x98, 0x49, 0x37, 0xCB, 0x0E, 0x96, 0x3B, 0xC3, 0x3D
+/// ]);
+/// ```
+///
+/// There are no NIST vectors for Hash_DRBG in this crate. `vectors/Hash_DRBG_openssl.rsp` holds
+/// outputs of the OpenSSL 3.5 Hash_DRBG in the CAVP layout, a cross-check against another
+/// implementation rather than conformance data:
+///
+/// ```
+/// # #[cfg(feature = "cavp")] {
+/// use simd_sha1::cavp::{self, Mechanism};
+///
+/// let vectors = cavp::parse_drbg(include_str!("../vectors/Hash_DRBG_openssl.rsp")).unwrap();
+///
+/// assert_eq!(vectors.len(), 24);
+/// assert!(cavp::run_drbg(Mechanism::HashDrbg, &vectors).passed());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashDrbg {
+    v: [u8; SEED_LEN],
+    c: [u8; SEED_LEN],
+    reseed_counter: u64,
+    prediction_resistance: bool,
+}
+
+impl HashDrbg {
+    /// Instantiates from `entropy_input`, `nonce` and `personalization`. Prediction resistance can
+    /// only be requested from [`generate`](HashDrbg::generate) if `prediction_resistance` is set.
+    pub fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8], prediction_resistance: bool) -> Result<HashDrbg, Error> {
+        if entropy_input.len() < MIN_ENTROPY {
+            return Err(Error::InsufficientEntropy)
+        }
+        let v = hash_df(&[entropy_input, nonce, personalization]);
+        let c = hash_df(&[&[0x00], &v]);
+        return Ok(HashDrbg { v, c, reseed_counter: 1, prediction_resistance })
+    }
+
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), Error> {
+        if entropy_input.len() < MIN_ENTROPY {
+            return Err(Error::InsufficientEntropy)
+        }
+        self.v = hash_df(&[&[0x01], &self.v, entropy_input, additional_input]);
+        self.c = hash_df(&[&[0x00], &self.v]);
+        self.reseed_counter = 1;
+        return Ok(())
+    }
+
+    /// Fills `output`. Passing fresh entropy in `prediction_resistance` requests prediction
+    /// resistance: the instance is reseeded with it and `additional_input` first.
+    pub fn generate(&mut self, output: &mut [u8], mut additional_input: &[u8], prediction_resistance: Option<&[u8]>) -> Result<(), Error> {
+        check_request(output.len(), self.reseed_counter, self.prediction_resistance, prediction_resistance)?;
+        if let Some(entropy_input) = prediction_resistance {
+            self.reseed(entropy_input, additional_input)?;
+            additional_input = &[];
+        }
+
+        if !additional_input.is_empty() {
+            let w = hash(&[&[0x02], &self.v, additional_input]);
+            add(&mut self.v, &w);
+        }
+
+        let mut data = self.v;
+        for chunk in output.chunks_mut(20) {
+            chunk.copy_from_slice(&hash(&[&data])[..chunk.len()]);
+            add(&mut data, &[1]);
+        }
+
+        let h = hash(&[&[0x03], &self.v]);
+        let c = self.c;
+        add(&mut self.v, &h);
+        add(&mut self.v, &c);
+        add(&mut self.v, &self.reseed_counter.to_be_bytes());
+        self.reseed_counter += 1;
+        return Ok(())
+    }
+}
+
+/// HMAC_DRBG (SP 800-90A section 10.1.2).
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::drbg::HmacDrbg;
+///
+/// let entropy = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F];
+/// let nonce = [0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27];
+/// let mut drbg = HmacDrbg::instantiate(&entropy, &nonce, &[], true).unwrap();
+///
+/// let mut output = [0; 80];
+/// drbg.generate(&mut output, &[], Some(&entropy.map(|byte| byte | 0x80))).unwrap();
+/// drbg.generate(&mut output, &[], Some(&entropy.map(|byte| byte | 0x90))).unwrap();
+///
+/// assert_eq!(output[..20], [
+///     0xE1, 0x3A, 0xFC, 0x6E, 0x7A, 0xD3, 0xE8, 0xCC, 0x43, 0x3C,
+///     0x39, 0x58, 0x04, 0xF7, 0x00, 0x97, 0x98, 0xC4, 0x42, 0x6F
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HmacDrbg {
+    key: Key,
+    v: [u8; 20],
+    reseed_counter: u64,
+    prediction_resistance: bool,
+}
+
+impl HmacDrbg {
+    /// Instantiates from `entropy_input`, `nonce` and `personalization`. Prediction resistance can
+    /// only be requested from [`generate`](HmacDrbg::generate) if `prediction_resistance` is set.
+    pub fn instantiate(entropy_input: &[u8], nonce: &[u8], personalization: &[u8], prediction_resistance: bool) -> Result<HmacDrbg, Error> {
+        if entropy_input.len() < MIN_ENTROPY {
+            return Err(Error::InsufficientEntropy)
+        }
+        let mut drbg = HmacDrbg { key: Key::new(&[0x00; 20]), v: [0x01; 20], reseed_counter: 1, prediction_resistance };
+        drbg.update(&[entropy_input, nonce, personalization]);
+        return Ok(drbg)
+    }
+
+    pub fn reseed(&mut self, entropy_input: &[u8], additional_input: &[u8]) -> Result<(), Error> {
+        if entropy_input.len() < MIN_ENTROPY {
+            return Err(Error::InsufficientEntropy)
+        }
+        self.update(&[entropy_input, additional_input]);
+        self.reseed_counter = 1;
+        return Ok(())
+    }
+
+    /// Fills `output`. Passing fresh entropy in `prediction_resistance` requests prediction
+    /// resistance: the instance is reseeded with it and `additional_input` first.
+    pub fn generate(&mut self, output: &mut [u8], mut additional_input: &[u8], prediction_resistance: Option<&[u8]>) -> Result<(), Error> {
+        check_request(output.len(), self.reseed_counter, self.prediction_resistance, prediction_resistance)?;
+        if let Some(entropy_input) = prediction_resistance {
+            self.reseed(entropy_input, additional_input)?;
+            additional_input = &[];
+        }
+
+        if !additional_input.is_empty() {
+            self.update(&[additional_input]);
+        }
+        for chunk in output.chunks_mut(20) {
+            self.v = self.key.mac(&self.v);
+            chunk.copy_from_slice(&self.v[..chunk.len()]);
+        }
+        self.update(&[additional_input]);
+        self.reseed_counter += 1;
+        return Ok(())
+    }
+
+    /// HMAC_DRBG_Update with the concatenation of `provided` as the provided data.
+    fn update(&mut self, provided: &[&[u8]]) {
+        let empty = provided.iter().all(|part| part.is_empty());
+        for round in [0x00, 0x01] {
+            if round == 0x01 && empty {
+                break
+            }
+            let mut hasher = self.key.hasher();
+            hasher.update(&self.v);
+            hasher.update(&[round]);
+            for part in provided {
+                hasher.update(part);
+            }
+            self.key = Key::new(&hasher.finalize());
+            self.v = self.key.mac(&self.v);
+        }
+    }
+}
+
+fn check_request(len: usize, reseed_counter: u64, supported: bool, prediction_resistance: Option<&[u8]>) -> Result<(), Error> {
+    if len > MAX_REQUEST {
+        return Err(Error::RequestTooLarge)
+    }
+    if prediction_resistance.is_some() && !supported {
+        return Err(Error::PredictionResistanceUnsupported)
+    }
+    if prediction_resistance.is_none() && reseed_counter > RESEED_INTERVAL {
+        return Err(Error::ReseedRequired)
+    }
+    return Ok(())
+}
+
+/// SHA1 of the concatenation of `parts`.
+fn hash(parts: &[&[u8]]) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    return hasher.finalize()
+}
+
+/// Hash_df: derives `seedlen` bytes from the concatenation of `parts`.
+fn hash_df(parts: &[&[u8]]) -> [u8; SEED_LEN] {
+    let mut output = [0; SEED_LEN];
+    for (counter, chunk) in (1u8..).zip(output.chunks_mut(20)) {
+        let mut hasher = Sha1::new();
+        hasher.update(&[counter]);
+        hasher.update(&(SEED_LEN as u32 * 8).to_be_bytes());
+        for part in parts {
+            hasher.update(part);
+        }
+        chunk.copy_from_slice(&hasher.finalize()[..chunk.len()]);
+    }
+    return output
+}
+
+/// `value = (value + addend) mod 2^(8 * value.len())`, both big-endian; `v + x mod 2^seedlen` for
+/// the Hash_DRBG state, and XKEY arithmetic in [`fips186_2`](crate::fips186_2).
+pub(crate) fn add(value: &mut [u8], addend: &[u8]) {
+    let mut carry = 0u16;
+    for (i, byte) in value.iter_mut().rev().enumerate() {
+        let digit = addend.len().checked_sub(i + 1).map_or(0, |index| addend[index]);
+        let sum = *byte as u16 + digit as u16 + carry;
+        *byte = sum as u8;
+        carry = sum >> 8;
+    }
+}
@@ -0,0 +1,194 @@
+//! The FIPS 186-2 pseudorandom number generator (change notice 1, appendix 3.1), as used for DSA
+//! private keys and by EAP-SIM and EAP-AKA.
+//!
+//! The generator keeps a secret XKEY of `b` bits, 160 to 512. Each step derives two 160-bit words
+//! with the function G, the SHA1 compression function applied once to a zero-extended block without
+//! any padding, and advances XKEY with them. An optional XSEED is mixed into every step.
+
+use std::fmt;
+
+use crate::drbg::add;
+
+/// The chaining value `t` of G for x (private key) generation, the SHA1 initial value.
+pub const T_X: [u32; 5] = crate::IV;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// XKEY is not 160 to 512 bits long.
+    InvalidXkeyLength,
+    /// XSEED is longer than XKEY.
+    XseedTooLong,
+    /// The input of G is longer than 512 bits.
+    InputTooLong,
+    /// The modulus `q` is zero.
+    ZeroModulus,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::InvalidXkeyLength => write!(f, "XKEY must be 160 to 512 bits"),
+            Error::XseedTooLong => write!(f, "XSEED is longer than XKEY"),
+            Error::InputTooLong => write!(f, "G takes at most 512 bits"),
+            Error::ZeroModulus => write!(f, "q is zero"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// G(t, c): one compression of `c` followed by zeros to 512 bits, from the chaining value `t`.
+///
+/// Fails with [`Error::InputTooLong`] if `c` is longer than 64 bytes.
+pub fn g(t: [u32; 5], c: &[u8]) -> Result<[u8; 20], Error> {
+    if c.len() > 64 {
+        return Err(Error::InputTooLong)
+    }
+    let mut block = [0; 64];
+    block[..c.len()].copy_from_slice(c);
+    let mut state = t;
+    crate::compress(&mut state, &[block]);
+
+    let mut output = [0; 20];
+    for (bytes, h) in output.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&h.to_be_bytes());
+    }
+    return Ok(output)
+}
+
+/// The x-generation algorithm, producing 320 bits per step.
+///
+/// # Examples
+///
+/// The example of the change notice, without XSEED:
+///
+/// ```
+/// use simd_sha1::fips186_2::Generator;
+///
+/// let xkey = [
+///     0xBD, 0x02, 0x9B, 0xBE, 0x7F, 0x51, 0x96, 0x0B, 0xCF, 0x9E,
+///     0xDB, 0x2B, 0x61, 0xF0, 0x6F, 0x0F, 0xEB, 0x5A, 0x38, 0xB6
+/// ];
+/// let mut generator = Generator::new(&xkey).unwrap();
+///
+/// let x0 = generator.next(None).unwrap();
+///
+/// assert_eq!(x0[..20], [
+///     0x20, 0x70, 0xB3, 0x22, 0x3D, 0xBA, 0x37, 0x2F, 0xDE, 0x1C,
+///     0x0F, 0xFC, 0x7B, 0x2E, 0x3B, 0x49, 0x8B, 0x26, 0x06, 0x14
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generator {
+    xkey: Vec<u8>,
+}
+
+impl Generator {
+    /// Starts from the secret `xkey`, of `b / 8` bytes.
+    ///
+    /// Fails with [`Error::InvalidXkeyLength`] if `xkey` is not 20 to 64 bytes long.
+    ///
+    /// ```
+    /// use simd_sha1::fips186_2::{Error, Generator};
+    ///
+    /// assert_eq!(Generator::new(&[0; 19]), Err(Error::InvalidXkeyLength));
+    /// assert_eq!(Generator::new(&[0; 65]), Err(Error::InvalidXkeyLength));
+    /// assert_eq!(Generator::new(&[0; 20]).unwrap().next(Some(&[0; 21])), Err(Error::XseedTooLong));
+    /// ```
+    pub fn new(xkey: &[u8]) -> Result<Generator, Error> {
+        if !(20..=64).contains(&xkey.len()) {
+            return Err(Error::InvalidXkeyLength)
+        }
+        return Ok(Generator { xkey: xkey.to_vec() })
+    }
+
+    /// Returns the current XKEY, e.g. to archive the generator state.
+    pub fn xkey(&self) -> &[u8] {
+        return &self.xkey
+    }
+
+    /// Returns `w_0 || w_1`, where `w_i = G(t, XKEY + XSEED mod 2^b)` and XKEY becomes
+    /// `1 + XKEY + w_i mod 2^b` after each.
+    ///
+    /// Fails with [`Error::XseedTooLong`] if `xseed` is longer than XKEY, leaving XKEY unchanged.
+    pub fn next(&mut self, xseed: Option<&[u8]>) -> Result<[u8; 40], Error> {
+        let xseed = xseed.unwrap_or_default();
+        if xseed.len() > self.xkey.len() {
+            return Err(Error::XseedTooLong)
+        }
+        let mut output = [0; 40];
+        for w in output.chunks_mut(20) {
+            let mut xval = self.xkey.clone();
+            add(&mut xval, xseed);
+            w.copy_from_slice(&g(T_X, &xval)?);
+            add(&mut self.xkey, w);
+            add(&mut self.xkey, &[1]);
+        }
+        return Ok(output)
+    }
+
+    /// Returns a DSA private key `x = w_0 || w_1 mod q` for the 160-bit prime `q`.
+    ///
+    /// Fails with [`Error::ZeroModulus`] if `q` is zero, or [`Error::XseedTooLong`] as [`next`](Self::next)
+    /// does, without changing XKEY.
+    ///
+    /// # Examples
+    ///
+    /// The example of the change notice, whose `w_0 || w_1` is
+    /// `2070B322…8B260614 3C6C18BA…A3275116`, reduced by the `q` of the FIPS 186-2 DSA example:
+    ///
+    /// ```
+    /// use simd_sha1::fips186_2::Generator;
+    ///
+    /// let xkey = [
+    ///     0xBD, 0x02, 0x9B, 0xBE, 0x7F, 0x51, 0x96, 0x0B, 0xCF, 0x9E,
+    ///     0xDB, 0x2B, 0x61, 0xF0, 0x6F, 0x0F, 0xEB, 0x5A, 0x38, 0xB6
+    /// ];
+    /// let q = [
+    ///     0xC7, 0x73, 0x21, 0x8C, 0x73, 0x7E, 0xC8, 0xEE, 0x99, 0x3B,
+    ///     0x4F, 0x2D, 0xED, 0x30, 0xF4, 0x8E, 0xDA, 0xCE, 0x91, 0x5F
+    /// ];
+    ///
+    /// let x = Generator::new(&xkey).unwrap().next_x(&q, None).unwrap();
+    ///
+    /// assert_eq!(x, [
+    ///     0xA3, 0x09, 0xA9, 0x75, 0x79, 0x5A, 0x03, 0xF4, 0x52, 0x02,
+    ///     0xB2, 0x5A, 0xCB, 0x0D, 0xF2, 0xC5, 0xAB, 0xA7, 0x4D, 0x6B
+    /// ]);
+    /// assert_eq!(Generator::new(&xkey).unwrap().next(None).unwrap()[20..], [
+    ///     0x3C, 0x6C, 0x18, 0xBA, 0xCB, 0x0F, 0x6C, 0x55, 0xBA, 0xBB,
+    ///     0x13, 0x78, 0x8E, 0x20, 0xD7, 0x37, 0xA3, 0x27, 0x51, 0x16
+    /// ]);
+    /// ```
+    pub fn next_x(&mut self, q: &[u8; 20], xseed: Option<&[u8]>) -> Result<[u8; 20], Error> {
+        if q.iter().all(|&byte| byte == 0) {
+            return Err(Error::ZeroModulus)
+        }
+        return Ok(reduce(&self.next(xseed)?, q))
+    }
+}
+
+/// `value mod modulus`, by shift and subtract over the bits of `value`.
+fn reduce(value: &[u8], modulus: &[u8; 20]) -> [u8; 20] {
+    // one spare byte holds the bit shifted out of the remainder before it is subtracted
+    let mut remainder = [0u8; 21];
+    let mut modulus_wide = [0u8; 21];
+    modulus_wide[1..].copy_from_slice(modulus);
+    for bit in (0..value.len() * 8).map(|index| value[index / 8] >> (7 - index % 8) & 1) {
+        let mut carry = bit;
+        for byte in remainder.iter_mut().rev() {
+            let shifted = *byte >> 7;
+            *byte = *byte << 1 | carry;
+            carry = shifted;
+        }
+        if remainder >= modulus_wide {
+            let mut borrow = 0i16;
+            for (byte, digit) in remainder.iter_mut().zip(modulus_wide).rev() {
+                let difference = *byte as i16 - digit as i16 - borrow;
+                *byte = difference.rem_euclid(256) as u8;
+                borrow = (difference < 0) as i16;
+            }
+        }
+    }
+    return remainder[1..].try_into().unwrap()
+}
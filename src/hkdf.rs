@@ -0,0 +1,167 @@
+//! HKDF with HMAC-SHA1 (RFC 5869).
+//!
+//! [`expand`] keys HMAC with the PRK once; each 20-byte block of output is then a MAC of
+//! `T(i - 1) || info || i` from the cached midstates, see [`hmac::Key`](crate::hmac::Key).
+
+use std::fmt;
+
+use crate::hmac::Key;
+
+const H_LEN: usize = 20;
+
+/// Longest output of [`expand`], 255 blocks.
+pub const MAX_OUTPUT: usize = 255 * H_LEN;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// More than [`MAX_OUTPUT`] bytes were requested.
+    OutputTooLong,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::OutputTooLong => write!(f, "output longer than {MAX_OUTPUT} bytes"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// HKDF-Extract: the pseudorandom key `HMAC(salt, ikm)`. An empty `salt` is the same as none,
+/// which RFC 5869 replaces with 20 zero bytes.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::hkdf;
+///
+/// // RFC 5869 test case 4
+/// let ikm = [0x0B; 11];
+/// let salt = [0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C];
+///
+/// assert_eq!(hkdf::extract(&salt, &ikm), [
+///     0x9B, 0x6C, 0x18, 0xC4, 0x32, 0xA7, 0xBF, 0x8F, 0x0E, 0x71,
+///     0xC8, 0xEB, 0x88, 0xF4, 0xB3, 0x0B, 0xAA, 0x2B, 0xA2, 0x43
+/// ]);
+///
+/// // RFC 5869 test case 7, salt not provided
+/// assert_eq!(hkdf::extract(&[], &[0x0C; 22]), [
+///     0x2A, 0xDC, 0xCA, 0xDA, 0x18, 0x77, 0x9E, 0x7C, 0x20, 0x77,
+///     0xAD, 0x2E, 0xB1, 0x9D, 0x3F, 0x3E, 0x73, 0x13, 0x85, 0xDD
+/// ]);
+/// assert_eq!(hkdf::extract(&[0; 20], &[0x0C; 22]), hkdf::extract(&[], &[0x0C; 22]));
+/// ```
+pub fn extract(salt: &[u8], ikm: &[u8]) -> [u8; 20] {
+    // HMAC zero-pads its key to a block, so no salt and 20 zero bytes are the same key
+    return Key::new(salt).mac(ikm)
+}
+
+/// HKDF-Expand: fills `output` from the pseudorandom key `prk` and `info`.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::hkdf;
+///
+/// // RFC 5869 test case 4
+/// let prk = [
+///     0x9B, 0x6C, 0x18, 0xC4, 0x32, 0xA7, 0xBF, 0x8F, 0x0E, 0x71,
+///     0xC8, 0xEB, 0x88, 0xF4, 0xB3, 0x0B, 0xAA, 0x2B, 0xA2, 0x43
+/// ];
+/// let info = [0xF0, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8, 0xF9];
+/// let mut okm = [0; 42];
+///
+/// hkdf::expand(&prk, &info, &mut okm).unwrap();
+///
+/// assert_eq!(okm, [
+///     0x08, 0x5A, 0x01, 0xEA, 0x1B, 0x10, 0xF3, 0x69, 0x33, 0x06,
+///     0x8B, 0x56, 0xEF, 0xA5, 0xAD, 0x81, 0xA4, 0xF1, 0x4B, 0x82,
+///     0x2F, 0x5B, 0x09, 0x15, 0x68, 0xA9, 0xCD, 0xD4, 0xF1, 0x55,
+///     0xFD, 0xA2, 0xC2, 0x2E, 0x42, 0x24, 0x78, 0xD3, 0x05, 0xF3,
+///     0xF8, 0x96
+/// ]);
+///
+/// // the last block has counter 255
+/// let mut okm = [0; hkdf::MAX_OUTPUT];
+/// hkdf::expand(&prk, &info, &mut okm).unwrap();
+/// assert_eq!(okm[hkdf::MAX_OUTPUT - 20..], [
+///     0x95, 0x3C, 0xC4, 0x7D, 0xF6, 0x76, 0x10, 0xD3, 0xC9, 0xDA,
+///     0x03, 0xA2, 0x85, 0x4F, 0x3B, 0xEF, 0x5A, 0xFF, 0xFA, 0x4A
+/// ]);
+/// assert_eq!(hkdf::expand(&prk, &info, &mut [0; hkdf::MAX_OUTPUT + 1]), Err(hkdf::Error::OutputTooLong));
+/// ```
+pub fn expand(prk: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    if output.len() > MAX_OUTPUT {
+        return Err(Error::OutputTooLong)
+    }
+
+    let key = Key::new(prk);
+    let mut t = [0; H_LEN];
+    for (index, chunk) in output.chunks_mut(H_LEN).enumerate() {
+        // at most 255 blocks, so the counter does not wrap
+        let counter = index as u8 + 1;
+        let mut hasher = key.hasher();
+        if index > 0 {
+            hasher.update(&t);
+        }
+        hasher.update(info);
+        hasher.update(&[counter]);
+        t = hasher.finalize();
+        chunk.copy_from_slice(&t[..chunk.len()]);
+    }
+    return Ok(())
+}
+
+/// Extract then expand.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::hkdf;
+///
+/// // RFC 5869 test case 5
+/// let ikm: Vec<u8> = (0x00..=0x4F).collect();
+/// let salt: Vec<u8> = (0x60..=0xAF).collect();
+/// let info: Vec<u8> = (0xB0..=0xFF).collect();
+/// let mut okm = [0; 82];
+///
+/// hkdf::hkdf(&salt, &ikm, &info, &mut okm).unwrap();
+///
+/// assert_eq!(okm, [
+///     0x0B, 0xD7, 0x70, 0xA7, 0x4D, 0x11, 0x60, 0xF7, 0xC9, 0xF1,
+///     0x2C, 0xD5, 0x91, 0x2A, 0x06, 0xEB, 0xFF, 0x6A, 0xDC, 0xAE,
+///     0x89, 0x9D, 0x92, 0x19, 0x1F, 0xE4, 0x30, 0x56, 0x73, 0xBA,
+///     0x2F, 0xFE, 0x8F, 0xA3, 0xF1, 0xA4, 0xE5, 0xAD, 0x79, 0xF3,
+///     0xF3, 0x34, 0xB3, 0xB2, 0x02, 0xB2, 0x17, 0x3C, 0x48, 0x6E,
+///     0xA3, 0x7C, 0xE3, 0xD3, 0x97, 0xED, 0x03, 0x4C, 0x7F, 0x9D,
+///     0xFE, 0xB1, 0x5C, 0x5E, 0x92, 0x73, 0x36, 0xD0, 0x44, 0x1F,
+///     0x4C, 0x43, 0x00, 0xE2, 0xCF, 0xF0, 0xD0, 0x90, 0x0B, 0x52,
+///     0xD3, 0xB4
+/// ]);
+///
+/// // RFC 5869 test case 6, empty salt and info
+/// hkdf::hkdf(&[], &[0x0B; 22], &[], &mut okm[..42]).unwrap();
+///
+/// assert_eq!(okm[..42], [
+///     0x0A, 0xC1, 0xAF, 0x70, 0x02, 0xB3, 0xD7, 0x61, 0xD1, 0xE5,
+///     0x52, 0x98, 0xDA, 0x9D, 0x05, 0x06, 0xB9, 0xAE, 0x52, 0x05,
+///     0x72, 0x20, 0xA3, 0x06, 0xE0, 0x7B, 0x6B, 0x87, 0xE8, 0xDF,
+///     0x21, 0xD0, 0xEA, 0x00, 0x03, 0x3D, 0xE0, 0x39, 0x84, 0xD3,
+///     0x49, 0x18
+/// ]);
+///
+/// // RFC 5869 test case 7
+/// hkdf::hkdf(&[], &[0x0C; 22], &[], &mut okm[..42]).unwrap();
+///
+/// assert_eq!(okm[..42], [
+///     0x2C, 0x91, 0x11, 0x72, 0x04, 0xD7, 0x45, 0xF3, 0x50, 0x0D,
+///     0x63, 0x6A, 0x62, 0xF6, 0x4F, 0x0A, 0xB3, 0xBA, 0xE5, 0x48,
+///     0xAA, 0x53, 0xD4, 0x23, 0xB0, 0xD1, 0xF2, 0x7E, 0xBB, 0xA6,
+///     0xF5, 0xE5, 0x67, 0x3A, 0x08, 0x1D, 0x70, 0xCC, 0xE7, 0xAC,
+///     0xFC, 0x48
+/// ]);
+/// ```
+pub fn hkdf(salt: &[u8], ikm: &[u8], info: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    return expand(&extract(salt, ikm), info, output)
+}
@@ -0,0 +1,98 @@
+//! HMAC-SHA1 (RFC 2104).
+//!
+//! The padded key fills exactly one block on each side of the MAC, so [`Key`] compresses both once
+//! and keeps the two chaining values. Every MAC under the key starts from them instead of rehashing
+//! the key, which is what makes repeated MACs under one key (PBKDF2, HKDF, DRBGs, TLS PRF) cheap.
+
+use crate::Sha1;
+
+const BLOCK: usize = 64;
+
+/// A key with its inner and outer midstates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    inner: [u32; 5],
+    outer: [u32; 5],
+}
+
+impl Key {
+    /// Keys longer than a block are hashed first, as RFC 2104 specifies.
+    pub fn new(key: &[u8]) -> Key {
+        let mut padded = [0; BLOCK];
+        if key.len() > BLOCK {
+            padded[..20].copy_from_slice(&crate::hash(key));
+        } else {
+            padded[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = crate::IV;
+        crate::compress(&mut inner, &[padded.map(|byte| byte ^ 0x36)]);
+        let mut outer = crate::IV;
+        crate::compress(&mut outer, &[padded.map(|byte| byte ^ 0x5C)]);
+        return Key { inner, outer }
+    }
+
+    /// Starts an incremental MAC under this key.
+    pub fn hasher(&self) -> Hmac {
+        return Hmac { inner: Sha1::with_iv(self.inner, BLOCK as u64), outer: self.outer }
+    }
+
+    /// Returns the MAC of `message`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simd_sha1::hmac::Key;
+    ///
+    /// let key = Key::new("key".as_bytes());
+    ///
+    /// assert_eq!(key.mac("abc".as_bytes()), simd_sha1::hmac::hmac("key".as_bytes(), "abc".as_bytes()));
+    /// ```
+    pub fn mac(&self, message: &[u8]) -> [u8; 20] {
+        let mut hasher = self.hasher();
+        hasher.update(message);
+        return hasher.finalize()
+    }
+}
+
+/// Incremental HMAC over a message fed in pieces.
+#[derive(Debug, Clone)]
+pub struct Hmac {
+    inner: Sha1,
+    outer: [u32; 5],
+}
+
+impl Hmac {
+    pub fn new(key: &[u8]) -> Hmac {
+        return Key::new(key).hasher()
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.inner.update(bytes);
+    }
+
+    pub fn finalize(self) -> [u8; 20] {
+        let mut outer = Sha1::with_iv(self.outer, BLOCK as u64);
+        outer.update(&self.inner.finalize());
+        return outer.finalize()
+    }
+}
+
+/// Returns the HMAC-SHA1 of `message` under `key`.
+///
+/// # Examples
+///
+/// ```
+/// // RFC 2202 test case 2
+/// let expect = [
+///     0xEF, 0xFC, 0xDF, 0x6A, 0xE5, 0xEB, 0x2F, 0xA2, 0xD2, 0x74,
+///     0x16, 0xD5, 0xF1, 0x84, 0xDF, 0x9C, 0x25, 0x9A, 0x7C, 0x79
+/// ];
+///
+/// let mac = simd_sha1::hmac::hmac("Jefe".as_bytes(), "what do ya want for nothing?".as_bytes());
+///
+/// assert_eq!(mac, expect);
+/// ```
+pub fn hmac(key: &[u8], message: &[u8]) -> [u8; 20] {
+    return Key::new(key).mac(message)
+}
@@ -2,23 +2,141 @@
 //! SHA1 implementation with simd.
 //! 
 //! The algorithm of implementation was published in [there](https://www.intel.com/content/www/us/en/developer/articles/technical/improving-the-performance-of-the-secure-hash-algorithm-1.html) by Maxim Loktyukhin
+#![allow(clippy::needless_return, clippy::zero_prefixed_literal)]
+#![cfg_attr(all(target_arch = "arm", any(target_feature = "neon", feature = "arm-detect")), feature(stdarch_arm_neon_intrinsics, arm_target_feature))]
+
+// The NEON and SHA1 backends read message bytes as little-endian vector lanes. They have not been
+// run on a big-endian target, so such builds stop here instead of returning wrong digests.
+#[cfg(all(target_endian = "big", any(target_arch = "aarch64", target_arch = "arm"), any(target_feature = "neon", target_feature = "sha2", feature = "arm-detect")))]
+compile_error!("the ARM NEON and SHA1 backends support little-endian targets only");
+
+pub mod bencode;
+pub mod bittorrent;
+#[cfg(feature = "cavp")]
+pub mod cavp;
+pub mod drbg;
+pub mod fips186_2;
+pub mod hkdf;
+pub mod hmac;
+pub mod merkle;
+pub mod ocsp;
+pub mod pkcs1;
+pub mod pkix;
+#[cfg(feature = "paranoid")]
+pub mod paranoid;
+#[cfg(feature = "research")]
+pub mod research;
+#[cfg(feature = "self_test")]
+pub mod self_test;
+pub mod sha0;
+pub mod tls_prf;
+
+mod streaming;
+pub use streaming::{hash_bits, Sha1};
+
+/// The initial chaining value of SHA1, H0 to H4.
+pub const IV: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+#[cfg(feature = "parallel")]
+mod parallel;
+#[cfg(feature = "parallel")]
+pub use parallel::par_hash_many;
 
 #[cfg(target_feature = "sha")]
 mod sha1_x86;
 #[cfg(target_feature = "sha")]
-pub use sha1_x86::hash;
+pub use sha1_x86::{compress, hash, hash_many};
+#[cfg(target_feature = "sha")]
+pub(crate) use sha1_x86::compress_sha0;
 
-#[cfg(target_feature = "sha2")]
+#[cfg(any(target_feature = "sha2", all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon"))))]
 mod sha1_arm;
 #[cfg(target_feature = "sha2")]
-pub use sha1_arm::hash;
+pub use sha1_arm::{compress, hash, hash_many};
+#[cfg(target_feature = "sha2")]
+pub(crate) use sha1_arm::compress_sha0;
 
-#[cfg(all(not(target_feature = "sha"), target_feature = "sse2"))]
+#[cfg(all(not(target_feature = "sha"), target_feature = "avx2"))]
+mod sha1_avx2;
+#[cfg(all(not(target_feature = "sha"), target_feature = "avx2"))]
+pub use sha1_avx2::{compress, hash};
+
+#[cfg(all(target_feature = "sse2", any(not(target_feature = "sha"), feature = "paranoid", feature = "cavp")))]
+#[cfg_attr(any(target_feature = "sha", target_feature = "avx2"), allow(dead_code))]
 mod sha1_sse;
+#[cfg(all(not(target_feature = "sha"), not(target_feature = "avx2"), target_feature = "sse2"))]
+pub use sha1_sse::{compress, hash};
+#[cfg(all(not(target_feature = "sha"), target_feature = "sse2"))]
+pub(crate) use sha1_sse::compress_sha0;
 #[cfg(all(not(target_feature = "sha"), target_feature = "sse2"))]
-pub use sha1_sse::hash;
+pub use sha1_sse::hash_many;
 
-#[cfg(all(not(target_feature = "sha2"), target_feature = "neon"))]
+#[cfg(any(all(any(not(target_feature = "sha2"), feature = "paranoid", feature = "cavp"), target_feature = "neon"), all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon"))))]
+#[cfg_attr(target_feature = "sha2", allow(dead_code))]
 mod sha1_neon;
 #[cfg(all(not(target_feature = "sha2"), target_feature = "neon"))]
-pub use sha1_neon::hash;
\ No newline at end of file
+pub use sha1_neon::{compress, hash, hash_many};
+#[cfg(all(not(target_feature = "sha2"), target_feature = "neon"))]
+pub(crate) use sha1_neon::compress_sha0;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod sha1_wasm;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub use sha1_wasm::{compress, hash, hash_many};
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub(crate) use sha1_wasm::compress_sha0;
+
+#[cfg(any(test, feature = "research", feature = "paranoid", feature = "cavp", not(any(target_feature = "sse2", target_feature = "neon", all(target_arch = "wasm32", target_feature = "simd128")))))]
+#[cfg_attr(any(test, feature = "research", feature = "paranoid", feature = "cavp"), allow(dead_code))]
+mod sha1_scalar;
+#[cfg(all(not(any(target_feature = "sse2", target_feature = "neon", all(target_arch = "wasm32", target_feature = "simd128"))), not(all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon")))))]
+pub use sha1_scalar::{compress, hash};
+#[cfg(all(not(any(target_feature = "sse2", target_feature = "neon", all(target_arch = "wasm32", target_feature = "simd128"))), not(all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon")))))]
+pub(crate) use sha1_scalar::compress_sha0;
+#[cfg(all(not(target_arch = "riscv64"), not(any(target_feature = "sse2", target_feature = "neon", all(target_arch = "wasm32", target_feature = "simd128"))), not(all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon")))))]
+pub use sha1_scalar::hash_many;
+
+#[cfg(target_arch = "riscv64")]
+mod sha1_rvv;
+#[cfg(target_arch = "riscv64")]
+pub use sha1_rvv::hash_many;
+
+#[cfg(all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon")))]
+mod sha1_armv7;
+#[cfg(all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon")))]
+pub use sha1_armv7::{compress, hash, hash_many};
+#[cfg(all(target_arch = "arm", target_os = "linux", feature = "arm-detect", not(target_feature = "neon")))]
+pub(crate) use sha1_armv7::compress_sha0;
+
+/// The selected backend checked against the portable one, which doctests cannot reach.
+#[cfg(test)]
+mod tests {
+    /// Lengths around the padding boundaries, then 0 to 4 blocks in no particular order, 101 messages
+    /// in all: not a multiple of any lane count, and more than any RVV `VLMAX`.
+    fn mixed_messages() -> Vec<Vec<u8>> {
+        let boundaries = [0, 1, 55, 56, 63, 64, 65, 119, 120, 127, 128, 1000];
+        let lengths = boundaries.into_iter().chain((0..89).map(|index| index * 37 % 300));
+        return lengths.enumerate().map(|(index, length)| (0..length).map(|byte| (byte * 7 + index) as u8).collect()).collect()
+    }
+
+    #[test]
+    fn hash_many_matches_scalar() {
+        let messages = mixed_messages();
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        let expect: Vec<[u8; 20]> = messages.iter().map(|message| crate::sha1_scalar::hash(message)).collect();
+
+        assert_eq!(crate::hash_many(&messages), expect);
+        // every batch size up to 40 hits the partial groups and strip tails of each backend
+        for count in 0..=40 {
+            assert_eq!(crate::hash_many(&messages[..count]), expect[..count], "{count} messages");
+            assert_eq!(crate::hash_many(&messages[count..]), expect[count..], "from message {count}");
+        }
+    }
+
+    #[test]
+    fn hash_matches_scalar() {
+        for message in mixed_messages() {
+            assert_eq!(crate::hash(&message), crate::sha1_scalar::hash(&message), "{} bytes", message.len());
+        }
+    }
+}
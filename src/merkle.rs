@@ -0,0 +1,179 @@
+//! Binary Merkle trees over SHA1.
+//!
+//! Leaves and inner nodes are domain separated as in RFC 6962: a leaf digest is `SHA1(0x00 || data)`
+//! and an inner node is `SHA1(0x01 || left || right)`. All nodes of a level are independent inputs,
+//! so every level is hashed with one call to [`hash_many`](crate::hash_many).
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// How a level with an odd number of nodes is reduced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OddNode {
+    /// The last node moves up to the next level unchanged, as in RFC 6962.
+    Promote,
+    /// The last node is paired with itself, as in Bitcoin.
+    ///
+    /// **Warning:** duplicating the last node lets trees with different leaf counts share a root
+    /// (CVE-2012-2459): repeating the last leaf of an odd level gives the same tree. A root in this
+    /// mode identifies a list of leaves only together with its leaf count, so callers must commit to
+    /// the count alongside the root and check it.
+    ///
+    /// ```
+    /// use simd_sha1::merkle::{MerkleTree, OddNode};
+    ///
+    /// let three = ["a".as_bytes(), "b".as_bytes(), "c".as_bytes()];
+    /// let four = ["a".as_bytes(), "b".as_bytes(), "c".as_bytes(), "c".as_bytes()];
+    ///
+    /// let root = MerkleTree::from_leaves(&three, OddNode::Duplicate).root();
+    /// assert_eq!(MerkleTree::from_leaves(&four, OddNode::Duplicate).root(), root);
+    ///
+    /// let root = MerkleTree::from_leaves(&three, OddNode::Promote).root();
+    /// assert_ne!(MerkleTree::from_leaves(&four, OddNode::Promote).root(), root);
+    /// ```
+    Duplicate,
+}
+
+/// Returns the digest of the leaf holding `data`.
+pub fn leaf_hash(data: &[u8]) -> [u8; 20] {
+    let mut message = Vec::with_capacity(data.len() + 1);
+    message.push(LEAF_PREFIX);
+    message.extend_from_slice(data);
+    return crate::hash(&message)
+}
+
+/// Returns the digest of the inner node with children `left` and `right`.
+pub fn node_hash(left: &[u8; 20], right: &[u8; 20]) -> [u8; 20] {
+    return crate::hash(&node_message(left, right))
+}
+
+#[inline]
+fn node_message(left: &[u8; 20], right: &[u8; 20]) -> [u8; 41] {
+    let mut message = [0; 41];
+    message[0] = NODE_PREFIX;
+    message[01..21].copy_from_slice(left);
+    message[21..41].copy_from_slice(right);
+    return message
+}
+
+/// A Merkle tree with all of its levels, leaves first.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::merkle::{MerkleTree, OddNode};
+///
+/// let leaves = ["a".as_bytes(), "b".as_bytes(), "c".as_bytes()];
+/// let tree = MerkleTree::from_leaves(&leaves, OddNode::Promote);
+///
+/// let proof = tree.proof(2).unwrap();
+/// assert!(proof.verify(&simd_sha1::merkle::leaf_hash("c".as_bytes()), &tree.root(), OddNode::Promote));
+/// assert!(!proof.verify(&simd_sha1::merkle::leaf_hash("d".as_bytes()), &tree.root(), OddNode::Promote));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 20]>>,
+    odd: OddNode,
+}
+
+impl MerkleTree {
+    /// Builds the tree over the given leaf data.
+    pub fn from_leaves(leaves: &[&[u8]], odd: OddNode) -> MerkleTree {
+        let messages: Vec<Vec<u8>> = leaves.iter().map(|data| [&[LEAF_PREFIX], *data].concat()).collect();
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        return MerkleTree::from_leaf_digests(crate::hash_many(&messages), odd)
+    }
+
+    /// Builds the tree over leaves that are already hashed with [`leaf_hash`].
+    pub fn from_leaf_digests(digests: Vec<[u8; 20]>, odd: OddNode) -> MerkleTree {
+        let mut levels = vec![digests];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = &levels[levels.len() - 1];
+            let messages: Vec<[u8; 41]> = level
+                .chunks(2)
+                .filter(|pair| pair.len() == 2 || odd == OddNode::Duplicate)
+                .map(|pair| node_message(&pair[0], pair.last().unwrap()))
+                .collect();
+            let messages: Vec<&[u8]> = messages.iter().map(|message| &message[..]).collect();
+            let mut next = crate::hash_many(&messages);
+            if level.len() % 2 == 1 && odd == OddNode::Promote {
+                next.push(level[level.len() - 1]);
+            }
+            levels.push(next);
+        }
+        return MerkleTree { levels, odd }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        return self.levels[0].len()
+    }
+
+    pub fn odd_node(&self) -> OddNode {
+        return self.odd
+    }
+
+    /// Returns the root digest. The root of an empty tree is the SHA1 of the empty string.
+    pub fn root(&self) -> [u8; 20] {
+        return match self.levels[self.levels.len() - 1].first() {
+            Some(root) => *root,
+            None => crate::hash(&[]),
+        }
+    }
+
+    /// Returns the audit path of leaf `index`, or `None` if it is out of range.
+    pub fn proof(&self, index: usize) -> Option<Proof> {
+        if index >= self.leaf_count() {
+            return None
+        }
+        let mut path = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                path.push(level[sibling]);
+            }
+            position /= 2;
+        }
+        return Some(Proof { index, leaf_count: self.leaf_count(), path })
+    }
+}
+
+/// The audit path of a leaf: one sibling digest per level, leaves first. Levels on which the node
+/// has no sibling contribute nothing, their reduction follows from `index` and `leaf_count`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proof {
+    pub index: usize,
+    pub leaf_count: usize,
+    pub path: Vec<[u8; 20]>,
+}
+
+impl Proof {
+    /// Checks that `leaf` (a digest from [`leaf_hash`]) is at `index` in the tree with `root`.
+    pub fn verify(&self, leaf: &[u8; 20], root: &[u8; 20], odd: OddNode) -> bool {
+        if self.index >= self.leaf_count {
+            return false
+        }
+        let mut digest = *leaf;
+        let mut position = self.index;
+        let mut count = self.leaf_count;
+        let mut path = self.path.iter();
+        while count > 1 {
+            if position ^ 1 >= count {
+                if odd == OddNode::Duplicate {
+                    digest = node_hash(&digest, &digest);
+                }
+            } else {
+                let Some(sibling) = path.next() else {
+                    return false
+                };
+                digest = match position % 2 {
+                    0 => node_hash(&digest, sibling),
+                    _ => node_hash(sibling, &digest),
+                };
+            }
+            position /= 2;
+            count = count.div_ceil(2);
+        }
+        return path.next().is_none() && digest == *root
+    }
+}
@@ -0,0 +1,226 @@
+//! OCSP `CertID` with SHA1 (RFC 6960 section 4.1.1).
+//!
+//! A `CertID` names a certificate by the hash of its issuer's name, the hash of its issuer's public
+//! key and its serial number. The key hash covers the same bits as a method 1 key identifier, see
+//! [`pkix::key_identifier`].
+
+use std::collections::HashMap;
+
+use crate::pkix::{self, Der, Error, INTEGER, NULL, OBJECT_IDENTIFIER, OCTET_STRING, SEQUENCE};
+
+/// Tags of the optional fields of `TBSRequest` before `requestList`: `[0] version` and
+/// `[1] requestorName`.
+const REQUEST_PREFIX_TAGS: [u8; 2] = [0xA0, 0xA1];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CertId {
+    /// SHA1 of the DER issuer name.
+    pub issuer_name_hash: [u8; 20],
+    /// SHA1 of the issuer's `subjectPublicKey` bits.
+    pub issuer_key_hash: [u8; 20],
+    /// Contents of the DER INTEGER, as in the certificate.
+    pub serial_number: Vec<u8>,
+}
+
+impl CertId {
+    /// Builds the `CertID` of `subject`, issued by `issuer`, both DER certificates. The name hash
+    /// is taken over the issuer's subject name, which must be the DER issuer name of `subject`.
+    ///
+    /// # Examples
+    ///
+    /// The `CertID` `openssl ocsp -issuer ca.pem -cert leaf.pem -req_text` prints:
+    ///
+    /// ```
+    /// use simd_sha1::ocsp::CertId;
+    /// use simd_sha1::pkix::Error;
+    ///
+    /// let issuer = include_bytes!("../vectors/ca.der");
+    /// let subject = include_bytes!("../vectors/leaf.der");
+    ///
+    /// let cert_id = CertId::new(issuer, subject).unwrap();
+    ///
+    /// assert_eq!(cert_id.issuer_name_hash, [
+    ///     0xD6, 0x7E, 0x7D, 0xA3, 0x9B, 0xD2, 0x64, 0x04, 0x10, 0x1C,
+    ///     0x42, 0x50, 0x63, 0x89, 0x2F, 0x34, 0x28, 0x7E, 0x81, 0x0B
+    /// ]);
+    /// assert_eq!(cert_id.issuer_key_hash, [
+    ///     0x9E, 0xA0, 0x09, 0x3E, 0x29, 0xFC, 0xAD, 0x7B, 0x27, 0xC9,
+    ///     0xAA, 0x5E, 0x5B, 0x7D, 0xC1, 0x0C, 0x30, 0x16, 0xCA, 0xF6
+    /// ]);
+    /// assert_eq!(cert_id.serial_number, [0x2A, 0x0B, 0x3C, 0x4D]);
+    /// assert_eq!(CertId::from_der(&cert_id.to_der()), Ok(cert_id));
+    ///
+    /// // the leaf did not issue the CA
+    /// assert_eq!(CertId::new(subject, issuer), Err(Error::IssuerMismatch));
+    /// ```
+    pub fn new(issuer: &[u8], subject: &[u8]) -> Result<CertId, Error> {
+        let issuer = pkix::tbs_certificate(issuer)?;
+        let subject = pkix::tbs_certificate(subject)?;
+        if subject.issuer.raw != issuer.subject.raw {
+            return Err(Error::IssuerMismatch)
+        }
+        return Ok(CertId {
+            issuer_name_hash: crate::hash(issuer.subject.raw),
+            issuer_key_hash: pkix::key_identifier(issuer.subject_public_key_info.raw)?,
+            serial_number: subject.serial_number.value.to_vec(),
+        })
+    }
+
+    /// Encodes the `CertID`, with NULL algorithm parameters as most responders send them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simd_sha1::ocsp::CertId;
+    ///
+    /// let cert_id = CertId { issuer_name_hash: [0x11; 20], issuer_key_hash: [0x22; 20], serial_number: vec![0x05] };
+    /// let der = cert_id.to_der();
+    ///
+    /// assert_eq!(der[..13], [0x30, 0x3A, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00]);
+    /// assert_eq!(CertId::from_der(&der), Ok(cert_id));
+    /// ```
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut algorithm = Vec::new();
+        pkix::encode_tlv(OBJECT_IDENTIFIER, &pkix::SHA1_OID, &mut algorithm);
+        pkix::encode_tlv(NULL, &[], &mut algorithm);
+
+        let mut fields = Vec::new();
+        pkix::encode_tlv(SEQUENCE, &algorithm, &mut fields);
+        pkix::encode_tlv(OCTET_STRING, &self.issuer_name_hash, &mut fields);
+        pkix::encode_tlv(OCTET_STRING, &self.issuer_key_hash, &mut fields);
+        pkix::encode_tlv(INTEGER, &self.serial_number, &mut fields);
+
+        let mut der = Vec::new();
+        pkix::encode_tlv(SEQUENCE, &fields, &mut der);
+        return der
+    }
+
+    /// Parses a DER `CertID`, which must use SHA1.
+    pub fn from_der(der: &[u8]) -> Result<CertId, Error> {
+        let mut outer = Der::new(der);
+        let cert_id = outer.expect(SEQUENCE)?;
+        outer.finish()?;
+
+        let mut fields = Der::new(cert_id.value);
+        let mut algorithm = Der::new(fields.expect(SEQUENCE)?.value);
+        if algorithm.expect(OBJECT_IDENTIFIER)?.value != pkix::SHA1_OID {
+            return Err(Error::UnsupportedAlgorithm)
+        }
+        if !algorithm.is_empty() && !algorithm.expect(NULL)?.value.is_empty() {
+            return Err(Error::InvalidLength)
+        }
+        algorithm.finish()?;
+        let issuer_name_hash = fields.expect(OCTET_STRING)?.value.try_into().map_err(|_| Error::InvalidLength)?;
+        let issuer_key_hash = fields.expect(OCTET_STRING)?.value.try_into().map_err(|_| Error::InvalidLength)?;
+        let serial_number = fields.expect(INTEGER)?.value.to_vec();
+        fields.finish()?;
+        return Ok(CertId { issuer_name_hash, issuer_key_hash, serial_number })
+    }
+}
+
+/// Returns the `CertID` of every `Request` in a DER `OCSPRequest`.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::ocsp::{self, CertId};
+///
+/// // `openssl ocsp -issuer ca.pem -cert leaf.pem -no_nonce -reqout ocsp_request.der`
+/// let request = include_bytes!("../vectors/ocsp_request.der");
+/// let cert_id = CertId::new(include_bytes!("../vectors/ca.der"), include_bytes!("../vectors/leaf.der")).unwrap();
+///
+/// assert_eq!(ocsp::parse_request(request), Ok(vec![cert_id]));
+/// assert!(ocsp::parse_request(&request[..request.len() - 1]).is_err());
+/// ```
+pub fn parse_request(der: &[u8]) -> Result<Vec<CertId>, Error> {
+    let mut outer = Der::new(der);
+    let request = outer.expect(SEQUENCE)?;
+    outer.finish()?;
+
+    // the optional signature after tbsRequest is not looked at
+    let tbs_request = Der::new(request.value).expect(SEQUENCE)?;
+    let mut fields = Der::new(tbs_request.value);
+    for tag in REQUEST_PREFIX_TAGS {
+        if fields.peek()?.tag == tag {
+            fields.read()?;
+        }
+    }
+    let mut requests = Der::new(fields.expect(SEQUENCE)?.value);
+    let mut cert_ids = Vec::new();
+    while !requests.is_empty() {
+        // singleRequestExtensions may follow reqCert
+        let request = requests.expect(SEQUENCE)?;
+        cert_ids.push(CertId::from_der(Der::new(request.value).expect(SEQUENCE)?.raw)?);
+    }
+    return Ok(cert_ids)
+}
+
+/// Issuers known to a responder, each with a value of the caller's choosing, looked up by the
+/// issuer hashes of a `CertID`.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::ocsp::Issuers;
+///
+/// let mut issuers = Issuers::new();
+/// issuers.insert(include_bytes!("../vectors/ca.der"), "ca").unwrap();
+/// // a leaf certificate is not the issuer of anything in the request
+/// issuers.insert(include_bytes!("../vectors/leaf.der"), "leaf").unwrap();
+///
+/// let matched = issuers.match_request(include_bytes!("../vectors/ocsp_request.der")).unwrap();
+///
+/// assert_eq!(matched.len(), 1);
+/// assert_eq!(matched[0].0.serial_number, [0x2A, 0x0B, 0x3C, 0x4D]);
+/// assert_eq!(matched[0].1, Some(&"ca"));
+///
+/// let mut others = Issuers::new();
+/// others.insert(include_bytes!("../vectors/leaf.der"), "leaf").unwrap();
+/// assert_eq!(others.match_request(include_bytes!("../vectors/ocsp_request.der")).unwrap()[0].1, None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Issuers<T> {
+    by_hashes: HashMap<([u8; 20], [u8; 20]), T>,
+}
+
+impl<T> Issuers<T> {
+    pub fn new() -> Issuers<T> {
+        return Issuers { by_hashes: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        return self.by_hashes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.by_hashes.is_empty()
+    }
+
+    /// Adds the DER certificate `issuer`, returning the value it replaces if it was already known.
+    pub fn insert(&mut self, issuer: &[u8], value: T) -> Result<Option<T>, Error> {
+        let issuer = pkix::tbs_certificate(issuer)?;
+        let name_hash = crate::hash(issuer.subject.raw);
+        let key_hash = pkix::key_identifier(issuer.subject_public_key_info.raw)?;
+        return Ok(self.by_hashes.insert((name_hash, key_hash), value))
+    }
+
+    /// Returns the issuer of the certificate `cert_id` names, if known.
+    pub fn get(&self, cert_id: &CertId) -> Option<&T> {
+        return self.by_hashes.get(&(cert_id.issuer_name_hash, cert_id.issuer_key_hash))
+    }
+
+    /// Parses a DER `OCSPRequest` and pairs each `CertID` with its issuer, if known.
+    pub fn match_request(&self, request: &[u8]) -> Result<Vec<(CertId, Option<&T>)>, Error> {
+        let cert_ids = parse_request(request)?;
+        return Ok(cert_ids.into_iter().map(|cert_id| {
+            let issuer = self.get(&cert_id);
+            (cert_id, issuer)
+        }).collect())
+    }
+}
+
+impl<T> Default for Issuers<T> {
+    fn default() -> Issuers<T> {
+        return Issuers::new()
+    }
+}
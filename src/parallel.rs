@@ -0,0 +1,58 @@
+//! Batch hashing across threads.
+
+use std::thread;
+
+/// Inputs smaller than this in total are hashed on the calling thread.
+const MIN_BYTES_PER_THREAD: u64 = 1 << 16;
+
+/// Bytes charged per message on top of its length, for padding and per-message overhead.
+const MESSAGE_COST: u64 = 64;
+
+/// Hashes every message independently like [`hash_many`](crate::hash_many), spread across the
+/// available cores. The messages are split into contiguous runs of about the same number of bytes,
+/// and each thread hashes its run with `hash_many`. Digests are returned in input order.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::par_hash_many(&messages);
+///
+/// assert_eq!(digests, simd_sha1::hash_many(&messages));
+/// ```
+pub fn par_hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    // in u64, as the products below overflow a 32-bit usize for inputs of a few hundred MB
+    let total: u64 = messages.iter().map(|bytes| bytes.len() as u64 + MESSAGE_COST).sum();
+    let cores = thread::available_parallelism().map_or(1, |cores| cores.get());
+    let threads = u64::min(cores as u64, total / MIN_BYTES_PER_THREAD).max(1);
+    if threads == 1 {
+        return crate::hash_many(messages)
+    }
+
+    let mut runs = Vec::with_capacity(threads as usize);
+    let mut start = 0;
+    let mut bytes = 0u64;
+    for (index, message) in messages.iter().enumerate() {
+        bytes += message.len() as u64 + MESSAGE_COST;
+        if bytes * threads >= total * (runs.len() as u64 + 1) {
+            runs.push(start..index + 1);
+            start = index + 1;
+        }
+    }
+    if start < messages.len() {
+        runs.push(start..messages.len());
+    }
+
+    let mut digests = vec![[0; 20]; messages.len()];
+    thread::scope(|scope| {
+        let mut rest = &mut digests[..];
+        for run in runs {
+            let (output, tail) = rest.split_at_mut(run.len());
+            rest = tail;
+            let input = &messages[run];
+            scope.spawn(move || output.copy_from_slice(&crate::hash_many(input)));
+        }
+    });
+    return digests
+}
@@ -0,0 +1,188 @@
+//! Cross-checking digests on a second backend.
+//!
+//! A marginal CPU core can compute a wrong digest without any other sign of failure. [`Paranoid`]
+//! hashes with the selected backend as usual and recomputes a sample of the digests on an independent
+//! one: the SSE backend behind SHA-NI or AVX2, the NEON backend behind the ARM SHA1 instructions, and
+//! the portable backend everywhere else. AVX2 builds take `hash_many` from the SSE backend, so there
+//! it is checked on the portable one. Where the portable backend is also the primary one, it is run a
+//! second time, which still catches transient faults.
+//!
+//! On a mismatch both backends run again, up to the configured number of retries. A digest is returned
+//! once they agree; otherwise the call fails with [`CorruptionDetected`].
+//!
+//! Both backends run on the same core, one after the other. A core that is faulty in a way that hits
+//! both code paths alike (a broken rotate or adder, say) can corrupt both digests the same way, and
+//! then they agree on the wrong answer. The cross-check catches faults specific to one backend's
+//! instructions and transient faults, not a core that is consistently wrong.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// the second backend for `hash`
+#[cfg(all(target_feature = "sse2", any(target_feature = "sha", target_feature = "avx2")))]
+use crate::sha1_sse as cross;
+#[cfg(all(target_feature = "sha2", target_feature = "neon"))]
+use crate::sha1_neon as cross;
+#[cfg(not(any(all(target_feature = "sse2", any(target_feature = "sha", target_feature = "avx2")), all(target_feature = "sha2", target_feature = "neon"))))]
+use crate::sha1_scalar as cross;
+
+// the second backend for `hash_many`
+#[cfg(all(target_feature = "sse2", target_feature = "sha"))]
+use crate::sha1_sse as cross_many;
+#[cfg(all(target_feature = "sha2", target_feature = "neon"))]
+use crate::sha1_neon as cross_many;
+#[cfg(not(any(all(target_feature = "sse2", target_feature = "sha"), all(target_feature = "sha2", target_feature = "neon"))))]
+use crate::sha1_scalar as cross_many;
+
+/// Recomputes the digest of one message on a backend.
+type Rehash = fn(&[u8]) -> [u8; 20];
+
+/// Two backends kept disagreeing about a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CorruptionDetected {
+    /// Position of the message in the batch, 0 for [`Paranoid::hash`].
+    pub index: usize,
+    /// Length of the message in bytes.
+    pub len: usize,
+    /// Digest from the selected backend, in the last attempt.
+    pub primary: [u8; 20],
+    /// Digest from the cross-checking backend, in the last attempt.
+    pub secondary: [u8; 20],
+    /// Number of times both backends were run.
+    pub attempts: u32,
+}
+
+impl fmt::Display for CorruptionDetected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SHA1 backends disagree on message {} of {} bytes after {} attempts: ", self.index, self.len, self.attempts)?;
+        for byte in self.primary {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, " != ")?;
+        for byte in self.secondary {
+            write!(f, "{byte:02x}")?;
+        }
+        return Ok(())
+    }
+}
+
+impl std::error::Error for CorruptionDetected {}
+
+/// Hashes like [`hash`](crate::hash) and [`hash_many`](crate::hash_many), cross-checking one in
+/// `one_in` messages on a second backend.
+///
+/// The sample is taken by counting messages, so the first message and every `one_in`-th after it are
+/// checked, across threads sharing the checker.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::paranoid::Paranoid;
+///
+/// let paranoid = Paranoid::new(1, 2);
+/// let messages = ["abc".as_bytes(), "".as_bytes()];
+///
+/// assert_eq!(paranoid.hash("abc".as_bytes()).unwrap(), simd_sha1::hash("abc".as_bytes()));
+/// assert_eq!(paranoid.hash_many(&messages).unwrap(), simd_sha1::hash_many(&messages));
+/// assert_eq!(paranoid.checked(), 3);
+/// assert_eq!(paranoid.mismatches(), 0);
+/// ```
+///
+/// Sampling one in four messages:
+///
+/// ```
+/// use simd_sha1::paranoid::Paranoid;
+///
+/// let paranoid = Paranoid::new(4, 1);
+/// let messages = vec!["abc".as_bytes(); 10];
+///
+/// paranoid.hash_many(&messages).unwrap();
+/// assert_eq!(paranoid.checked(), 3);
+/// paranoid.hash_many(&messages[..2]).unwrap();
+/// assert_eq!(paranoid.checked(), 3);
+/// paranoid.hash("abc".as_bytes()).unwrap();
+/// assert_eq!(paranoid.checked(), 4);
+///
+/// // a rate wider than usize on 32-bit targets still samples only the first message
+/// let paranoid = Paranoid::new(1 << 32, 1);
+/// paranoid.hash_many(&messages).unwrap();
+/// assert_eq!(paranoid.checked(), 1);
+/// ```
+#[derive(Debug)]
+pub struct Paranoid {
+    one_in: u64,
+    retries: u32,
+    counter: AtomicU64,
+    checked: AtomicU64,
+    mismatches: AtomicU64,
+}
+
+impl Paranoid {
+    /// Cross-checks one in `one_in` messages, and runs both backends up to `retries` more times
+    /// when they disagree.
+    ///
+    /// # Panics
+    ///
+    /// If `one_in` is 0.
+    pub fn new(one_in: u64, retries: u32) -> Paranoid {
+        assert!(one_in > 0, "sampling rate must be at least 1 in 1");
+        return Paranoid { one_in, retries, counter: AtomicU64::new(0), checked: AtomicU64::new(0), mismatches: AtomicU64::new(0) }
+    }
+
+    /// Returns the number of messages cross-checked so far.
+    pub fn checked(&self) -> u64 {
+        return self.checked.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of disagreements seen so far, including those resolved by a retry.
+    pub fn mismatches(&self) -> u64 {
+        return self.mismatches.load(Ordering::Relaxed)
+    }
+
+    pub fn hash(&self, bytes: &[u8]) -> Result<[u8; 20], CorruptionDetected> {
+        let digest = crate::hash(bytes);
+        if !self.sample(1).contains(&0) {
+            return Ok(digest)
+        }
+        return self.check(0, bytes, [digest, cross::hash(bytes)], [crate::hash, cross::hash])
+    }
+
+    pub fn hash_many(&self, messages: &[&[u8]]) -> Result<Vec<[u8; 20]>, CorruptionDetected> {
+        let mut digests = crate::hash_many(messages);
+        let sampled = self.sample(messages.len());
+        let batch: Vec<&[u8]> = sampled.iter().map(|&index| messages[index]).collect();
+        let rehash: [Rehash; 2] = [|bytes| crate::hash_many(&[bytes])[0], |bytes| cross_many::hash_many(&[bytes])[0]];
+        for (&index, other) in sampled.iter().zip(cross_many::hash_many(&batch)) {
+            digests[index] = self.check(index, messages[index], [digests[index], other], rehash)?;
+        }
+        return Ok(digests)
+    }
+
+    /// Takes `count` messages off the counter and returns the indices of those to cross-check.
+    fn sample(&self, count: usize) -> Vec<usize> {
+        let start = self.counter.fetch_add(count as u64, Ordering::Relaxed);
+        let first = (self.one_in - start % self.one_in) % self.one_in;
+        // stepping in u64, `one_in` may not fit in a usize
+        let indices = std::iter::successors(Some(first), |index| index.checked_add(self.one_in));
+        let sampled: Vec<usize> = indices.take_while(|&index| index < count as u64).map(|index| index as usize).collect();
+        self.checked.fetch_add(sampled.len() as u64, Ordering::Relaxed);
+        return sampled
+    }
+
+    /// Compares the `primary` and `secondary` digests of `bytes`, recomputing both with `rehash` while
+    /// they disagree.
+    fn check(&self, index: usize, bytes: &[u8], digests: [[u8; 20]; 2], rehash: [Rehash; 2]) -> Result<[u8; 20], CorruptionDetected> {
+        let [mut primary, mut secondary] = digests;
+        let mut attempts = 1;
+        while primary != secondary {
+            self.mismatches.fetch_add(1, Ordering::Relaxed);
+            if attempts > self.retries {
+                return Err(CorruptionDetected { index, len: bytes.len(), primary, secondary, attempts })
+            }
+            primary = rehash[0](bytes);
+            secondary = rehash[1](bytes);
+            attempts += 1;
+        }
+        return Ok(primary)
+    }
+}
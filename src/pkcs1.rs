@@ -0,0 +1,246 @@
+//! MGF1 and the RSA encoding methods of PKCS #1 v2.2 (RFC 8017) with SHA1.
+//!
+//! Only the encodings are here: EME-OAEP turns a message into the integer to encrypt and back, and
+//! EMSA-PSS turns a message into the integer to sign and checks it after verification. The RSA
+//! operations are up to the caller, and so is the randomness: the OAEP seed and the PSS salt are
+//! parameters.
+//!
+//! MGF1 output is the concatenation of the hashes of `seed || counter` for successive counters.
+//! Those messages are independent, so they go through [`hash_many`](crate::hash_many) together.
+
+use std::fmt;
+
+const H_LEN: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The message does not fit in a modulus of this size.
+    MessageTooLong,
+    /// The modulus is too small for the hash and salt lengths.
+    ModulusTooShort,
+    /// More than `2^32` blocks of MGF1 output were requested.
+    MaskTooLong,
+    /// The encoded message is malformed. OAEP decoding does not say how, so that it cannot serve as
+    /// a padding oracle.
+    Decoding,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::MessageTooLong => write!(f, "message too long"),
+            Error::ModulusTooShort => write!(f, "modulus too short"),
+            Error::MaskTooLong => write!(f, "mask too long"),
+            Error::Decoding => write!(f, "decoding error"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// MGF1 with SHA1: `out_len` bytes of mask from `seed`. The 32-bit counter allows at most `2^32`
+/// blocks, beyond which RFC 8017 stops with "mask too long".
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkcs1;
+///
+/// assert_eq!(pkcs1::mgf1_sha1("foo".as_bytes(), 5).unwrap(), [0x1A, 0xC9, 0x07, 0x5C, 0xD4]);
+/// assert_eq!(pkcs1::mgf1_sha1("bar".as_bytes(), 5).unwrap(), [0xBC, 0x0C, 0x65, 0x5E, 0x01]);
+/// if usize::BITS == 64 {
+///     assert_eq!(pkcs1::mgf1_sha1("foo".as_bytes(), usize::MAX), Err(pkcs1::Error::MaskTooLong));
+/// }
+/// ```
+pub fn mgf1_sha1(seed: &[u8], out_len: usize) -> Result<Vec<u8>, Error> {
+    let block_count = out_len.div_ceil(H_LEN) as u64;
+    if block_count > 1 << 32 {
+        return Err(Error::MaskTooLong)
+    }
+    let blocks: Vec<Vec<u8>> = (0..block_count).map(|counter| [seed, &(counter as u32).to_be_bytes()].concat()).collect();
+    let messages: Vec<&[u8]> = blocks.iter().map(|block| block.as_slice()).collect();
+    let mut mask = crate::hash_many(&messages).concat();
+    mask.truncate(out_len);
+    return Ok(mask)
+}
+
+/// XORs the MGF1 mask of `seed` into `bytes`.
+fn apply_mask(bytes: &mut [u8], seed: &[u8]) -> Result<(), Error> {
+    let mask = mgf1_sha1(seed, bytes.len())?;
+    for (byte, mask) in bytes.iter_mut().zip(mask) {
+        *byte ^= mask;
+    }
+    return Ok(())
+}
+
+/// EME-OAEP encoding of `message` with `label` for a modulus of `k` bytes, using `seed` as the
+/// random seed.
+///
+/// # Examples
+///
+/// RSAES-OAEP example 1.1 of the PKCS #1 v2.1 test vectors (`oaep-vect.txt`), where EM is the
+/// decryption of the example ciphertext:
+///
+/// ```
+/// use simd_sha1::pkcs1;
+///
+/// let message = [
+///     0x66, 0x28, 0x19, 0x4E, 0x12, 0x07, 0x3D, 0xB0, 0x3B, 0xA9, 0x4C, 0xDA, 0x9E, 0xF9,
+///     0x53, 0x23, 0x97, 0xD5, 0x0D, 0xBA, 0x79, 0xB9, 0x87, 0x00, 0x4A, 0xFE, 0xFE, 0x34
+/// ];
+/// let seed = [
+///     0x18, 0xB7, 0x76, 0xEA, 0x21, 0x06, 0x9D, 0x69, 0x77, 0x6A,
+///     0x33, 0xE9, 0x6B, 0xAD, 0x48, 0xE1, 0xDD, 0xA0, 0xA5, 0xEF
+/// ];
+///
+/// let encoded = pkcs1::oaep_encode(128, &message, &[], &seed).unwrap();
+///
+/// assert_eq!(encoded, [
+///     0x00, 0x10, 0x16, 0xE9, 0x3A, 0x98, 0xAC, 0x3A, 0xF3, 0xA8, 0xC4, 0x8F, 0xC0, 0x1E, 0x87, 0xBD,
+///     0x70, 0x00, 0xDB, 0x8B, 0xDF, 0x69, 0x1F, 0x90, 0xE4, 0xDB, 0xAB, 0xB0, 0x3F, 0x76, 0x00, 0x37,
+///     0x65, 0xF5, 0x5B, 0x7E, 0xD7, 0xA2, 0x02, 0x63, 0x5E, 0xA9, 0x2F, 0xED, 0xA9, 0x46, 0x5E, 0xC4,
+///     0x0F, 0x3E, 0x0F, 0x64, 0xB8, 0xB0, 0xE4, 0x44, 0x95, 0x62, 0x45, 0xC5, 0xD5, 0xD0, 0x68, 0xCB,
+///     0x0B, 0x1D, 0xEC, 0xA8, 0x8B, 0x2F, 0x99, 0xAE, 0x0D, 0xF4, 0xE8, 0x0F, 0x01, 0x7C, 0x59, 0x5C,
+///     0xDB, 0xA4, 0xC9, 0x2F, 0x2F, 0x46, 0xF2, 0x5B, 0xDB, 0xF3, 0x52, 0xBE, 0xB9, 0x37, 0x66, 0xD2,
+///     0x45, 0x54, 0x10, 0x6F, 0x42, 0xD9, 0x32, 0x6D, 0x59, 0x33, 0x9C, 0xA2, 0x4F, 0x39, 0x2E, 0x73,
+///     0x8C, 0x70, 0x3D, 0xC8, 0x76, 0xE4, 0x02, 0xEE, 0x16, 0x54, 0x20, 0x95, 0xB7, 0x7A, 0x69, 0x94
+/// ]);
+/// assert_eq!(pkcs1::oaep_decode(128, &encoded, &[]).unwrap(), message);
+/// assert!(pkcs1::oaep_decode(128, &encoded, "label".as_bytes()).is_err());
+/// ```
+pub fn oaep_encode(k: usize, message: &[u8], label: &[u8], seed: &[u8; 20]) -> Result<Vec<u8>, Error> {
+    if k < 2 * H_LEN + 2 {
+        return Err(Error::ModulusTooShort)
+    }
+    if message.len() > k - 2 * H_LEN - 2 {
+        return Err(Error::MessageTooLong)
+    }
+
+    let mut encoded = vec![0; k];
+    let (masked_seed, db) = encoded[1..].split_at_mut(H_LEN);
+    let start = db.len() - message.len();
+    db[..H_LEN].copy_from_slice(&crate::hash(label));
+    db[start - 1] = 0x01;
+    db[start..].copy_from_slice(message);
+    apply_mask(db, seed)?;
+    masked_seed.copy_from_slice(seed);
+    apply_mask(masked_seed, db)?;
+    return Ok(encoded)
+}
+
+/// EME-OAEP decoding of `encoded` with `label` for a modulus of `k` bytes.
+///
+/// The padding checks do not branch on the content of `encoded`.
+pub fn oaep_decode(k: usize, encoded: &[u8], label: &[u8]) -> Result<Vec<u8>, Error> {
+    if k < 2 * H_LEN + 2 || encoded.len() != k {
+        return Err(Error::Decoding)
+    }
+
+    let mut encoded = encoded.to_vec();
+    let (y, rest) = encoded.split_at_mut(1);
+    let (seed, db) = rest.split_at_mut(H_LEN);
+    apply_mask(seed, db).map_err(|_| Error::Decoding)?;
+    apply_mask(db, seed).map_err(|_| Error::Decoding)?;
+
+    let label_hash = crate::hash(label);
+    let mut bad = y[0];
+    for (byte, expect) in db.iter().zip(label_hash) {
+        bad |= byte ^ expect;
+    }
+    // the message starts after the first 0x01 following the label hash; before it only zeros
+    let mut padding = 1u8;
+    let mut start = 0;
+    for (index, &byte) in db.iter().enumerate().skip(H_LEN) {
+        let one = (byte == 0x01) as u8;
+        let zero = (byte == 0x00) as u8;
+        start |= index & ((padding & one) as usize).wrapping_neg();
+        bad |= padding & !(one | zero) & 1;
+        padding &= !one;
+    }
+    bad |= padding;
+    if bad != 0 {
+        return Err(Error::Decoding)
+    }
+    return Ok(db[start + 1..].to_vec())
+}
+
+/// EMSA-PSS encoding of `message` with `salt` into `em_bits` bits, one less than the bit length of
+/// the modulus.
+///
+/// # Examples
+///
+/// RSASSA-PSS example 1.4 of the PKCS #1 v2.1 test vectors (`pss-vect.txt`), where EM is the
+/// example signature raised to the public exponent:
+///
+/// ```
+/// use simd_sha1::pkcs1;
+///
+/// let message = [0xBC, 0x65, 0x67, 0x47, 0xFA, 0x9E, 0xAF, 0xB3, 0xF0];
+/// let salt = [
+///     0x05, 0x6F, 0x00, 0x98, 0x5D, 0xE1, 0x4D, 0x8E, 0xF5, 0xCE,
+///     0xA9, 0xE8, 0x2F, 0x8C, 0x27, 0xBE, 0xF7, 0x20, 0x33, 0x5E
+/// ];
+///
+/// let encoded = pkcs1::pss_encode(&message, 1023, &salt).unwrap();
+///
+/// assert_eq!(encoded, [
+///     0x6A, 0xD8, 0x7A, 0xD6, 0xE7, 0x29, 0x6C, 0xD5, 0x95, 0x39, 0x6E, 0x4E, 0x73, 0xFA, 0x2F, 0xC1,
+///     0x25, 0x17, 0x2B, 0xA7, 0x10, 0x02, 0x42, 0xD8, 0xFF, 0xD8, 0xBA, 0x04, 0xB9, 0xB4, 0xF2, 0x2E,
+///     0x63, 0xF1, 0x8A, 0x15, 0xC0, 0x0C, 0xA7, 0xDA, 0x7B, 0x10, 0x00, 0xBC, 0x86, 0x92, 0x5D, 0x7F,
+///     0xDA, 0xC3, 0xE1, 0xD7, 0x8C, 0x1A, 0x04, 0x8C, 0x87, 0xA5, 0x6B, 0x17, 0xF6, 0xE4, 0x86, 0x43,
+///     0xAD, 0x4E, 0xE3, 0xB5, 0x19, 0x5F, 0xC1, 0xBC, 0x48, 0x40, 0xA9, 0xDC, 0xC7, 0x95, 0x1E, 0x58,
+///     0x79, 0x26, 0x5B, 0x83, 0x09, 0x5B, 0x5A, 0x01, 0x2B, 0x97, 0x2D, 0x48, 0xD7, 0x64, 0x21, 0x22,
+///     0xCD, 0x8B, 0xC1, 0x14, 0xB5, 0x0C, 0x5F, 0x9E, 0x74, 0xC0, 0xD9, 0x5C, 0x23, 0x9D, 0x44, 0xDA,
+///     0x34, 0x6D, 0xF0, 0x2D, 0x2D, 0x33, 0xD3, 0xBB, 0x70, 0xBB, 0x31, 0xD0, 0x51, 0x3F, 0x78, 0xBC
+/// ]);
+/// assert!(pkcs1::pss_verify(&message, &encoded, 1023, salt.len()));
+/// assert!(!pkcs1::pss_verify("massage".as_bytes(), &encoded, 1023, salt.len()));
+/// ```
+pub fn pss_encode(message: &[u8], em_bits: usize, salt: &[u8]) -> Result<Vec<u8>, Error> {
+    let em_len = em_bits.div_ceil(8);
+    if em_len < H_LEN + salt.len() + 2 {
+        return Err(Error::ModulusTooShort)
+    }
+
+    let h = pss_hash(message, salt);
+    let mut encoded = vec![0; em_len];
+    let (db, tail) = encoded.split_at_mut(em_len - H_LEN - 1);
+    let start = db.len() - salt.len();
+    db[start - 1] = 0x01;
+    db[start..].copy_from_slice(salt);
+    apply_mask(db, &h)?;
+    db[0] &= 0xFF >> (8 * em_len - em_bits);
+    tail[..H_LEN].copy_from_slice(&h);
+    tail[H_LEN] = 0xBC;
+    return Ok(encoded)
+}
+
+/// EMSA-PSS verification of `encoded` against `message`, for a salt of `salt_len` bytes.
+pub fn pss_verify(message: &[u8], encoded: &[u8], em_bits: usize, salt_len: usize) -> bool {
+    let em_len = em_bits.div_ceil(8);
+    if encoded.len() != em_len || em_len < H_LEN + salt_len + 2 || encoded[em_len - 1] != 0xBC {
+        return false
+    }
+    let (masked_db, tail) = encoded.split_at(em_len - H_LEN - 1);
+    if masked_db[0] & !(0xFF >> (8 * em_len - em_bits)) != 0 {
+        return false
+    }
+
+    let h = &tail[..H_LEN];
+    let mut db = masked_db.to_vec();
+    if apply_mask(&mut db, h).is_err() {
+        return false
+    }
+    db[0] &= 0xFF >> (8 * em_len - em_bits);
+    let (padding, salt) = db.split_at(db.len() - salt_len);
+    let Some((&separator, zeros)) = padding.split_last() else { return false };
+    if separator != 0x01 || zeros.iter().any(|&byte| byte != 0) {
+        return false
+    }
+    return pss_hash(message, salt) == h
+}
+
+/// `H = Hash(0x00 * 8 || Hash(message) || salt)`.
+fn pss_hash(message: &[u8], salt: &[u8]) -> [u8; 20] {
+    return crate::hash(&[&[0; 8], &crate::hash(message)[..], salt].concat())
+}
@@ -0,0 +1,412 @@
+//! SHA1 in X.509 and PKCS #1 v1.5: `DigestInfo`, key identifiers and certificate thumbprints.
+//!
+//! The DER walker reads just enough of a certificate to reach its `subjectPublicKeyInfo`: single-byte
+//! tags and definite lengths in their minimal encoding. Anything else is rejected rather than
+//! interpreted.
+
+use std::fmt;
+
+pub(crate) const SEQUENCE: u8 = 0x30;
+pub(crate) const INTEGER: u8 = 0x02;
+pub(crate) const BIT_STRING: u8 = 0x03;
+pub(crate) const OCTET_STRING: u8 = 0x04;
+pub(crate) const NULL: u8 = 0x05;
+pub(crate) const OBJECT_IDENTIFIER: u8 = 0x06;
+/// `[0] EXPLICIT`, the version of a certificate.
+const VERSION: u8 = 0xA0;
+
+/// DER of the object identifier 1.3.14.3.2.26 (id-sha1), without tag and length.
+pub const SHA1_OID: [u8; 5] = [0x2B, 0x0E, 0x03, 0x02, 0x1A];
+
+/// The `DigestInfo` for SHA1 up to its digest, as listed in RFC 8017 section 9.2.
+const DIGEST_INFO_PREFIX: [u8; 15] = [0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2B, 0x0E, 0x03, 0x02, 0x1A, 0x05, 0x00, 0x04, 0x14];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    UnexpectedEnd,
+    /// A tag other than the one the structure requires, or a multi-byte tag.
+    UnexpectedTag(u8),
+    /// An indefinite length, or one not in its shortest form.
+    InvalidLength,
+    TrailingData,
+    /// The digest algorithm is not SHA1.
+    UnsupportedAlgorithm,
+    /// Malformed PEM armor or base64.
+    Pem,
+    /// The certificate's issuer name is not the subject name of the issuer given.
+    IssuerMismatch,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Error::UnexpectedEnd => write!(f, "unexpected end of DER"),
+            Error::UnexpectedTag(tag) => write!(f, "unexpected tag 0x{tag:02x}"),
+            Error::InvalidLength => write!(f, "invalid DER length"),
+            Error::TrailingData => write!(f, "trailing data"),
+            Error::UnsupportedAlgorithm => write!(f, "digest algorithm is not SHA1"),
+            Error::Pem => write!(f, "invalid PEM"),
+            Error::IssuerMismatch => write!(f, "issuer name does not match"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// One DER element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tlv<'a> {
+    pub tag: u8,
+    /// The contents octets.
+    pub value: &'a [u8],
+    /// The whole encoding, tag and length included.
+    pub raw: &'a [u8],
+}
+
+/// Reads DER elements one after another.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkix::Der;
+///
+/// let mut der = Der::new(&[0x30, 0x03, 0x02, 0x01, 0x05]);
+/// let sequence = der.expect(0x30).unwrap();
+/// assert!(der.is_empty());
+///
+/// let integer = Der::new(sequence.value).expect(0x02).unwrap();
+/// assert_eq!(integer.value, [0x05]);
+///
+/// // a length of 2^32 - 1 bytes, past the end of the input and, on 32-bit targets, of memory
+/// assert!(Der::new(&[0x04, 0x84, 0xFF, 0xFF, 0xFF, 0xFF]).peek().is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Der<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    pub fn new(bytes: &'a [u8]) -> Der<'a> {
+        return Der { bytes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.bytes.is_empty()
+    }
+
+    /// Returns the next element without consuming it.
+    pub fn peek(&self) -> Result<Tlv<'a>, Error> {
+        let [tag, first, rest @ ..] = self.bytes else {
+            return Err(Error::UnexpectedEnd)
+        };
+        if tag & 0x1F == 0x1F {
+            return Err(Error::UnexpectedTag(*tag))
+        }
+        let (len, header) = match first {
+            0x00..=0x7F => (*first as usize, 2),
+            0x81..=0x84 => {
+                let count = (first & 0x7F) as usize;
+                let digits = rest.get(..count).ok_or(Error::UnexpectedEnd)?;
+                let len = digits.iter().fold(0usize, |len, &digit| len << 8 | digit as usize);
+                if digits[0] == 0 || len < 0x80 {
+                    return Err(Error::InvalidLength)
+                }
+                (len, 2 + count)
+            }
+            _ => return Err(Error::InvalidLength),
+        };
+        // four length bytes can exceed the address space on 32-bit targets
+        let end = header.checked_add(len).ok_or(Error::InvalidLength)?;
+        let raw = self.bytes.get(..end).ok_or(Error::UnexpectedEnd)?;
+        return Ok(Tlv { tag: *tag, value: &raw[header..], raw })
+    }
+
+    /// Consumes the next element.
+    pub fn read(&mut self) -> Result<Tlv<'a>, Error> {
+        let tlv = self.peek()?;
+        self.bytes = &self.bytes[tlv.raw.len()..];
+        return Ok(tlv)
+    }
+
+    /// Consumes the next element, which must have tag `tag`.
+    pub fn expect(&mut self, tag: u8) -> Result<Tlv<'a>, Error> {
+        let tlv = self.peek()?;
+        if tlv.tag != tag {
+            return Err(Error::UnexpectedTag(tlv.tag))
+        }
+        return self.read()
+    }
+
+    /// Checks that nothing is left.
+    pub fn finish(&self) -> Result<(), Error> {
+        if !self.is_empty() {
+            return Err(Error::TrailingData)
+        }
+        return Ok(())
+    }
+}
+
+/// Encodes the `DigestInfo` of a SHA1 digest, the value padded by EMSA-PKCS1-v1_5.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkix;
+///
+/// let digest = simd_sha1::hash("abc".as_bytes());
+/// let encoded = pkix::digest_info(&digest);
+///
+/// assert_eq!(encoded[..6], [0x30, 0x21, 0x30, 0x09, 0x06, 0x05]);
+/// assert_eq!(pkix::parse_digest_info(&encoded), Ok(digest));
+/// ```
+pub fn digest_info(digest: &[u8; 20]) -> [u8; 35] {
+    let mut encoded = [0; 35];
+    encoded[..15].copy_from_slice(&DIGEST_INFO_PREFIX);
+    encoded[15..].copy_from_slice(digest);
+    return encoded
+}
+
+/// Parses a SHA1 `DigestInfo` and returns the digest. The algorithm parameters may be NULL or, as
+/// RFC 8017 allows for SHA1, absent.
+pub fn parse_digest_info(der: &[u8]) -> Result<[u8; 20], Error> {
+    let mut outer = Der::new(der);
+    let digest_info = outer.expect(SEQUENCE)?;
+    outer.finish()?;
+
+    let mut fields = Der::new(digest_info.value);
+    let mut algorithm = Der::new(fields.expect(SEQUENCE)?.value);
+    if algorithm.expect(OBJECT_IDENTIFIER)?.value != SHA1_OID {
+        return Err(Error::UnsupportedAlgorithm)
+    }
+    if !algorithm.is_empty() && !algorithm.expect(NULL)?.value.is_empty() {
+        return Err(Error::InvalidLength)
+    }
+    algorithm.finish()?;
+    let digest = fields.expect(OCTET_STRING)?.value.try_into().map_err(|_| Error::InvalidLength)?;
+    fields.finish()?;
+    return Ok(digest)
+}
+
+/// Key identifier by method 1 of RFC 5280 section 4.2.1.2: the SHA1 of the `subjectPublicKey` bits
+/// of a DER `SubjectPublicKeyInfo`, without tag, length or unused-bits count.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkix;
+///
+/// // an Ed25519 key whose 32 bytes are all 0x01
+/// let mut spki = vec![0x30, 0x2A, 0x30, 0x05, 0x06, 0x03, 0x2B, 0x65, 0x70, 0x03, 0x21, 0x00];
+/// spki.extend([0x01; 32]);
+///
+/// assert_eq!(pkix::key_identifier(&spki), Ok(simd_sha1::hash(&[0x01; 32])));
+/// ```
+pub fn key_identifier(spki: &[u8]) -> Result<[u8; 20], Error> {
+    let mut outer = Der::new(spki);
+    let info = outer.expect(SEQUENCE)?;
+    outer.finish()?;
+
+    let mut fields = Der::new(info.value);
+    fields.expect(SEQUENCE)?;
+    let public_key = fields.expect(BIT_STRING)?;
+    fields.finish()?;
+    let [0, bits @ ..] = public_key.value else {
+        return Err(Error::InvalidLength)
+    };
+    return Ok(crate::hash(bits))
+}
+
+/// The fields of a `TBSCertificate` that identify the certificate and its key.
+pub(crate) struct TbsCertificate<'a> {
+    pub serial_number: Tlv<'a>,
+    pub issuer: Tlv<'a>,
+    pub subject: Tlv<'a>,
+    pub subject_public_key_info: Tlv<'a>,
+}
+
+/// Walks a DER certificate up to its `subjectPublicKeyInfo`.
+pub(crate) fn tbs_certificate(certificate: &[u8]) -> Result<TbsCertificate<'_>, Error> {
+    let mut outer = Der::new(certificate);
+    let certificate = outer.expect(SEQUENCE)?;
+    outer.finish()?;
+
+    let tbs_certificate = Der::new(certificate.value).expect(SEQUENCE)?;
+    let mut fields = Der::new(tbs_certificate.value);
+    if fields.peek()?.tag == VERSION {
+        fields.read()?;
+    }
+    let serial_number = fields.expect(INTEGER)?;
+    // signature
+    fields.expect(SEQUENCE)?;
+    let issuer = fields.expect(SEQUENCE)?;
+    // validity
+    fields.expect(SEQUENCE)?;
+    let subject = fields.expect(SEQUENCE)?;
+    let subject_public_key_info = fields.expect(SEQUENCE)?;
+    return Ok(TbsCertificate { serial_number, issuer, subject, subject_public_key_info })
+}
+
+/// Returns the DER `SubjectPublicKeyInfo` of a DER certificate.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkix;
+///
+/// // a P-256 CA certificate made with OpenSSL
+/// let certificate = include_bytes!("../vectors/ca.der");
+/// let spki = pkix::subject_public_key_info(certificate).unwrap();
+///
+/// assert_eq!(spki.len(), 91);
+/// // `openssl x509 -pubkey | openssl pkey -pubin -outform DER | sha1sum`
+/// assert_eq!(simd_sha1::hash(spki), [
+///     0x70, 0x71, 0xF0, 0x50, 0x04, 0x81, 0x5E, 0x3C, 0xA9, 0xC6,
+///     0x0D, 0xC1, 0x1D, 0xE7, 0x29, 0x80, 0x58, 0x7D, 0x46, 0x87
+/// ]);
+/// ```
+pub fn subject_public_key_info(certificate: &[u8]) -> Result<&[u8], Error> {
+    return Ok(tbs_certificate(certificate)?.subject_public_key_info.raw)
+}
+
+/// Method 1 key identifier of the key in a DER certificate: its subject key identifier, and the
+/// authority key identifier of the certificates it issues.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkix;
+///
+/// let certificate = include_bytes!("../vectors/ca.der");
+/// let key_identifier = pkix::certificate_key_identifier(certificate).unwrap();
+///
+/// // the subjectKeyIdentifier OpenSSL put in the certificate
+/// assert_eq!(key_identifier, [
+///     0x9E, 0xA0, 0x09, 0x3E, 0x29, 0xFC, 0xAD, 0x7B, 0x27, 0xC9,
+///     0xAA, 0x5E, 0x5B, 0x7D, 0xC1, 0x0C, 0x30, 0x16, 0xCA, 0xF6
+/// ]);
+/// let extension = pkix::subject_key_identifier_extension(&key_identifier);
+/// assert!(certificate.windows(extension.len()).any(|window| window == extension));
+/// ```
+pub fn certificate_key_identifier(certificate: &[u8]) -> Result<[u8; 20], Error> {
+    return key_identifier(subject_public_key_info(certificate)?)
+}
+
+/// DER value of a `subjectKeyIdentifier` extension.
+pub fn subject_key_identifier_extension(key_identifier: &[u8; 20]) -> [u8; 22] {
+    let mut encoded = [0; 22];
+    encoded[..2].copy_from_slice(&[OCTET_STRING, 20]);
+    encoded[2..].copy_from_slice(key_identifier);
+    return encoded
+}
+
+/// DER value of an `authorityKeyIdentifier` extension holding only the key identifier.
+pub fn authority_key_identifier_extension(key_identifier: &[u8; 20]) -> [u8; 24] {
+    let mut encoded = [0; 24];
+    // [0] IMPLICIT KeyIdentifier
+    encoded[..4].copy_from_slice(&[SEQUENCE, 22, 0x80, 20]);
+    encoded[4..].copy_from_slice(key_identifier);
+    return encoded
+}
+
+/// Appends the DER encoding of an element with tag `tag` and contents `value`.
+pub(crate) fn encode_tlv(tag: u8, value: &[u8], output: &mut Vec<u8>) {
+    output.push(tag);
+    if value.len() < 0x80 {
+        output.push(value.len() as u8);
+    } else {
+        let digits = value.len().to_be_bytes();
+        let skip = digits.iter().take_while(|&&digit| digit == 0).count();
+        output.push(0x80 | (digits.len() - skip) as u8);
+        output.extend(&digits[skip..]);
+    }
+    output.extend(value);
+}
+
+/// SHA1 thumbprint of a certificate given as DER or PEM. PEM input is recognized by its
+/// `-----BEGIN` line; the first block is used.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::pkix;
+///
+/// let der = [0x30, 0x03, 0x02, 0x01, 0x05];
+/// let pem = "-----BEGIN CERTIFICATE-----\nMAMCAQU=\n-----END CERTIFICATE-----\n";
+///
+/// assert_eq!(pkix::thumbprint(&der), Ok(simd_sha1::hash(&der)));
+/// assert_eq!(pkix::thumbprint(pem.as_bytes()), Ok(simd_sha1::hash(&der)));
+///
+/// // the contents of a PEM block are checked like DER input
+/// let pem = "-----BEGIN CERTIFICATE-----\nMAMCAQUA\n-----END CERTIFICATE-----\n";
+/// assert_eq!(pkix::thumbprint(pem.as_bytes()), Err(pkix::Error::TrailingData));
+/// let pem = "-----BEGIN CERTIFICATE-----\nAgEF\n-----END CERTIFICATE-----\n";
+/// assert_eq!(pkix::thumbprint(pem.as_bytes()), Err(pkix::Error::UnexpectedTag(0x02)));
+///
+/// // `openssl x509 -fingerprint -sha1` of a P-256 CA certificate
+/// assert_eq!(pkix::thumbprint(include_bytes!("../vectors/ca.der")), Ok([
+///     0x15, 0x6B, 0xC8, 0xE3, 0xF8, 0xE6, 0x98, 0x0D, 0x3E, 0x2D,
+///     0x3A, 0x30, 0xF8, 0xF7, 0x91, 0x92, 0x1E, 0x96, 0x5B, 0xBA
+/// ]));
+/// ```
+pub fn thumbprint(certificate: &[u8]) -> Result<[u8; 20], Error> {
+    let decoded;
+    let certificate = if certificate.trim_ascii_start().starts_with(b"-----BEGIN") {
+        let text = std::str::from_utf8(certificate).map_err(|_| Error::Pem)?;
+        decoded = pem_decode(text)?;
+        &decoded[..]
+    } else {
+        certificate
+    };
+    let mut der = Der::new(certificate);
+    der.expect(SEQUENCE)?;
+    der.finish()?;
+    return Ok(crate::hash(certificate))
+}
+
+/// Decodes the first PEM block of `text`, whatever its label.
+pub fn pem_decode(text: &str) -> Result<Vec<u8>, Error> {
+    let mut lines = text.lines().map(str::trim).skip_while(|line| !line.starts_with("-----BEGIN "));
+    let begin = lines.next().ok_or(Error::Pem)?;
+    let label = begin.strip_prefix("-----BEGIN ").and_then(|rest| rest.strip_suffix("-----")).ok_or(Error::Pem)?;
+    let end = format!("-----END {label}-----");
+
+    let mut base64 = String::new();
+    for line in lines {
+        if line == end {
+            return base64_decode(&base64)
+        }
+        // RFC 7468 explanatory text is not allowed inside, but header lines of the older format are
+        if line.contains(':') {
+            continue
+        }
+        base64 += line;
+    }
+    return Err(Error::Pem)
+}
+
+fn base64_decode(text: &str) -> Result<Vec<u8>, Error> {
+    let digits = text.trim_end_matches('=');
+    if !text.len().is_multiple_of(4) || text.len() - digits.len() > 2 {
+        return Err(Error::Pem)
+    }
+    let mut output = Vec::with_capacity(digits.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in digits.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return Err(Error::Pem),
+        };
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    return Ok(output)
+}
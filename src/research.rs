@@ -0,0 +1,124 @@
+//! Reduced-round and instrumented SHA1 for cryptanalysis.
+//!
+//! Everything here runs the portable round code one step at a time, so the full message schedule
+//! and the working state after every step can be observed. A compression reduced to `steps` steps
+//! runs steps `0..steps` and then adds the chaining value as usual. This module is for studying the
+//! compression function, not for speed.
+
+use crate::sha1_scalar::{self, choose, majority, parity};
+
+/// Number of steps of full SHA1.
+pub const STEPS: usize = 80;
+
+/// The message schedule and the working state of one compression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    /// The expanded message `w[0..80]`.
+    pub w: [u32; 80],
+    /// `(a, b, c, d, e)` after each step, `states[0]` being the state before the first step.
+    pub states: Vec<[u32; 5]>,
+    /// The chaining value after the feed-forward.
+    pub output: [u32; 5],
+}
+
+/// Hashes `message` with the compression function reduced to `steps` steps.
+///
+/// # Panics
+///
+/// If `steps` is more than [`STEPS`].
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::research;
+///
+/// let message = "abc".as_bytes();
+/// assert_eq!(research::hash_rounds(80, message), simd_sha1::hash(message));
+/// assert_ne!(research::hash_rounds(58, message), simd_sha1::hash(message));
+/// ```
+pub fn hash_rounds(steps: usize, message: &[u8]) -> [u8; 20] {
+    let padded = sha1_scalar::padding(message);
+    let (blocks, _) = padded.as_chunks::<64>();
+    let mut state = crate::IV;
+    compress_rounds(&mut state, blocks, steps);
+
+    let mut digest = [0; 20];
+    for (bytes, h) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&h.to_be_bytes());
+    }
+    return digest
+}
+
+/// [`compress`](crate::compress) reduced to `steps` steps.
+///
+/// # Panics
+///
+/// If `steps` is more than [`STEPS`].
+pub fn compress_rounds(state: &mut [u32; 5], blocks: &[[u8; 64]], steps: usize) {
+    for block in blocks {
+        *state = trace_block(*state, block, steps).output;
+    }
+}
+
+/// Traces every block of the padded `message`, each compression reduced to `steps` steps.
+///
+/// # Panics
+///
+/// If `steps` is more than [`STEPS`].
+pub fn trace(steps: usize, message: &[u8]) -> Vec<Trace> {
+    let padded = sha1_scalar::padding(message);
+    let (blocks, _) = padded.as_chunks::<64>();
+    let mut state = crate::IV;
+    let mut traces = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let trace = trace_block(state, block, steps);
+        state = trace.output;
+        traces.push(trace);
+    }
+    return traces
+}
+
+/// Runs one compression of `block` from the chaining value `state`, reduced to `steps` steps, and
+/// records the schedule and the state after every step.
+///
+/// # Panics
+///
+/// If `steps` is more than [`STEPS`].
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::research;
+///
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let trace = research::trace_block(simd_sha1::IV, &block, 80);
+/// assert_eq!(trace.w[0], 0x61626380);
+/// assert_eq!(trace.states.len(), 81);
+/// // FIPS 180-2 appendix A.1, state after step t = 0
+/// assert_eq!(trace.states[1], [0x0116FC33, 0x67452301, 0x7BF36AE2, 0x98BADCFE, 0x10325476]);
+/// assert_eq!(trace.output, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn trace_block(state: [u32; 5], block: &[u8; 64], steps: usize) -> Trace {
+    assert!(steps <= STEPS, "SHA1 has {STEPS} steps, {steps} requested");
+    let w = sha1_scalar::schedule::<1>(block);
+    let mut states = Vec::with_capacity(steps + 1);
+    states.push(state);
+    let mut abcde = state;
+    for t in 0..steps {
+        let (k, function): (u32, fn(u32, u32, u32) -> u32) = match t / 20 {
+            0 => (0x5A827999, choose),
+            1 => (0x6ED9EBA1, parity),
+            2 => (0x8F1BBCDC, majority),
+            _ => (0xCA62C1D6, parity),
+        };
+        abcde = sha1_scalar::compute(abcde, &w[t..t + 1], k, function);
+        states.push(abcde);
+    }
+
+    let output = std::array::from_fn(|i| abcde[i].wrapping_add(state[i]));
+    return Trace { w, states, output }
+}
@@ -0,0 +1,178 @@
+//! Power-on known-answer tests.
+//!
+//! The first call into the hashing API runs a set of known answers through the backend this crate
+//! was built with (or picked at startup with `arm-detect`): [`hash`](crate::hash),
+//! [`hash_many`](crate::hash_many), [`compress`](crate::compress) and the SHA-0 compression function.
+//! Other threads entering the API meanwhile wait for the result. If any answer is wrong, that call and
+//! every later one panics, so a broken backend never returns a digest. [`run`] performs the tests
+//! eagerly, e.g. at program start.
+//!
+//! The reduced-round functions of `research` are not covered.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Once;
+
+static ONCE: Once = Once::new();
+static STATUS: AtomicU8 = AtomicU8::new(Status::Untested as u8);
+static FAULT: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Set while this thread runs the tests, whose own calls into the API must not wait on them.
+    static RUNNING: Cell<bool> = const { Cell::new(false) };
+}
+
+const MESSAGES: [&str; 5] = [
+    "",
+    "abc",
+    "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+    "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+    "The quick brown fox jumps over the lazy dog",
+];
+
+const DIGESTS: [[u32; 5]; 5] = [
+    [0xDA39A3EE, 0x5E6B4B0D, 0x3255BFEF, 0x95601890, 0xAFD80709],
+    [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D],
+    [0x84983E44, 0x1C3BD26E, 0xBAAE4AA1, 0xF95129E5, 0xE54670F1],
+    [0xA49B2446, 0xA02C645B, 0xF419F995, 0xB6709125, 0x3A04A259],
+    [0x2FD4E1C6, 0x7A2D28FC, 0xED849EE1, 0xBB76E739, 0x1B93EB12],
+];
+
+/// SHA-0 of "abc".
+const SHA0_ABC: [u32; 5] = [0x0164B8A9, 0x14CD2A5E, 0x74C4F7FF, 0x082C4D97, 0xF1EDF880];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// Nothing has been hashed yet.
+    Untested,
+    Passed,
+    /// Every call into the hashing API panics.
+    Failed,
+}
+
+/// Returns the result of the self-test, without running it.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::self_test::{self, Status};
+///
+/// simd_sha1::hash("abc".as_bytes());
+///
+/// assert_eq!(self_test::status(), Status::Passed);
+/// ```
+pub fn status() -> Status {
+    return match STATUS.load(Ordering::Acquire) {
+        0 => Status::Untested,
+        1 => Status::Passed,
+        _ => Status::Failed,
+    }
+}
+
+/// Runs the self-test unless it has already run, and returns its result.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::self_test::{self, Status};
+///
+/// assert_eq!(self_test::run(), Status::Passed);
+/// ```
+pub fn run() -> Status {
+    ONCE.call_once(|| {
+        RUNNING.set(true);
+        // a backend that panics fails like one that returns a wrong digest
+        let passed = std::panic::catch_unwind(known_answers).unwrap_or(false);
+        let status = if passed { Status::Passed } else { Status::Failed };
+        RUNNING.set(false);
+        STATUS.store(status as u8, Ordering::Release);
+    });
+    return status()
+}
+
+/// Corrupts one expected digest of the known-answer tests, so that the self-test fails. This exists
+/// to test the failure path and has no effect once the self-test has run.
+///
+/// ```
+/// use std::panic::{catch_unwind, set_hook};
+///
+/// use simd_sha1::self_test::{self, Status};
+///
+/// self_test::inject_fault();
+///
+/// // every entry point refuses to hash, which can only be caught where panics unwind
+/// #[cfg(panic = "unwind")]
+/// {
+///     set_hook(Box::new(|_| {}));
+///     let block = [[0; 64]];
+///     for _ in 0..2 {
+///         assert!(catch_unwind(|| simd_sha1::hash("abc".as_bytes())).is_err());
+///         assert!(catch_unwind(|| simd_sha1::hash_many(&["abc".as_bytes()])).is_err());
+///         assert!(catch_unwind(|| simd_sha1::compress(&mut simd_sha1::IV, &block)).is_err());
+///         assert!(catch_unwind(|| simd_sha1::hash_bits("abc".as_bytes(), 20)).is_err());
+///         assert!(catch_unwind(|| simd_sha1::Sha1::new().finalize()).is_err());
+///         assert!(catch_unwind(|| simd_sha1::sha0::hash("abc".as_bytes())).is_err());
+///         assert!(catch_unwind(|| simd_sha1::sha0::compress(&mut simd_sha1::IV, &block)).is_err());
+///         #[cfg(feature = "parallel")]
+///         assert!(catch_unwind(|| simd_sha1::par_hash_many(&["abc".as_bytes()])).is_err());
+///         assert!(catch_unwind(|| simd_sha1::hmac::Key::new("key".as_bytes())).is_err());
+///         assert_eq!(self_test::status(), Status::Failed);
+///     }
+/// }
+///
+/// assert_eq!(self_test::run(), Status::Failed);
+/// assert_eq!(self_test::status(), Status::Failed);
+/// ```
+#[doc(hidden)]
+pub fn inject_fault() {
+    FAULT.store(true, Ordering::Relaxed);
+}
+
+/// Called on entry to every hashing function.
+///
+/// # Panics
+///
+/// If the self-test failed.
+#[inline]
+pub(crate) fn ensure() {
+    if STATUS.load(Ordering::Relaxed) == Status::Passed as u8 || RUNNING.get() {
+        return
+    }
+    if run() != Status::Passed {
+        panic!("SHA1 self-test failed, refusing to hash");
+    }
+}
+
+fn known_answers() -> bool {
+    let mut expect: Vec<[u8; 20]> = DIGESTS.iter().map(|&words| digest(words)).collect();
+    if FAULT.load(Ordering::Relaxed) {
+        expect[0][0] ^= 1;
+    }
+    let mut passed = MESSAGES.iter().zip(&expect).all(|(message, &expect)| crate::hash(message.as_bytes()) == expect);
+
+    // enough messages of mixed lengths to fill every lane of the multi-buffer kernels twice
+    let messages: Vec<&[u8]> = MESSAGES.iter().cycle().take(3 * MESSAGES.len()).map(|message| message.as_bytes()).collect();
+    let digests = crate::hash_many(&messages);
+    passed &= digests.iter().zip(expect.iter().cycle()).all(|(digest, expect)| digest == expect) && digests.len() == messages.len();
+
+    let mut block = [0; 64];
+    block[..3].copy_from_slice("abc".as_bytes());
+    block[3] = 0x80;
+    block[63] = 24;
+    let mut state = crate::IV;
+    crate::compress(&mut state, &[block]);
+    passed &= state == DIGESTS[1];
+    let mut state = crate::IV;
+    crate::compress_sha0(&mut state, &[block]);
+    passed &= state == SHA0_ABC;
+
+    return passed
+}
+
+fn digest(words: [u32; 5]) -> [u8; 20] {
+    let mut digest = [0; 20];
+    for (bytes, word) in digest.chunks_mut(4).zip(words) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    return digest
+}
@@ -0,0 +1,93 @@
+//! SHA-0, the original FIPS 180 hash that SHA1 replaced.
+//!
+//! SHA-0 differs from SHA1 only in its message expansion, `w[t] = w[t-3] ^ w[t-8] ^ w[t-14] ^ w[t-16]`
+//! without the one-bit rotation. It is broken and only meant for cryptanalysis and old formats.
+//! Every backend keeps its SHA1 rounds and swaps in a SHA-0 message schedule.
+
+use crate::Sha1;
+
+/// # Examples
+///
+/// ```
+/// let expect = [
+///     0x01, 0x64, 0xB8, 0xA9, 0x14, 0xCD, 0x2A, 0x5E, 0x74, 0xC4,
+///     0xF7, 0xFF, 0x08, 0x2C, 0x4D, 0x97, 0xF1, 0xED, 0xF8, 0x80
+/// ];
+///
+/// assert_eq!(simd_sha1::sha0::hash("abc".as_bytes()), expect);
+/// ```
+pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    let mut hasher = Sha0::new();
+    hasher.update(bytes);
+    return hasher.finalize()
+}
+
+/// Runs the SHA-0 compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    crate::compress_sha0(state, blocks);
+}
+
+/// Incremental SHA-0, with the same interface as [`Sha1`].
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::sha0::Sha0;
+///
+/// let mut hasher = Sha0::new();
+/// hasher.update("abcdbcdecdefdefgefghfghighij".as_bytes());
+/// hasher.update("hijkijkljklmklmnlmnomnopnopq".as_bytes());
+///
+/// let expect = [
+///     0xD2, 0x51, 0x6E, 0xE1, 0xAC, 0xFA, 0x5B, 0xAF, 0x33, 0xDF,
+///     0xC1, 0xC4, 0x71, 0xE4, 0x38, 0x44, 0x9E, 0xF1, 0x34, 0xC8
+/// ];
+/// assert_eq!(hasher.finalize(), expect);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha0(Sha1);
+
+impl Sha0 {
+    pub fn new() -> Sha0 {
+        return Sha0::with_iv(crate::IV, 0)
+    }
+
+    /// Starts from the chaining value `iv`, as if `processed_len` bytes had already been hashed.
+    ///
+    /// # Panics
+    ///
+    /// If `processed_len` is not a whole number of 64-byte blocks.
+    pub fn with_iv(iv: [u32; 5], processed_len: u64) -> Sha0 {
+        return Sha0(Sha1::with_compress(iv, processed_len, compress))
+    }
+
+    /// Returns the chaining value after the last complete block.
+    pub fn state(&self) -> [u32; 5] {
+        return self.0.state()
+    }
+
+    /// Returns the number of bytes hashed so far.
+    pub fn processed_len(&self) -> u64 {
+        return self.0.processed_len()
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    /// Bit-granular [`update`](Sha0::update), see [`Sha1::update_bits`].
+    pub fn update_bits(&mut self, bytes: &[u8], bit_len: u64) {
+        self.0.update_bits(bytes, bit_len);
+    }
+
+    pub fn finalize(self) -> [u8; 20] {
+        return self.0.finalize()
+    }
+}
+
+impl Default for Sha0 {
+    fn default() -> Sha0 {
+        return Sha0::new()
+    }
+}
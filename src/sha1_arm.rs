@@ -19,27 +19,70 @@ const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0x
 /// assert_eq!(digest, expect);
 /// ```
 pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
     let message = padding(bytes);
     unsafe {
-        let mut hash_value = (vld1q_u32(HASH_VALUE.as_ptr()), HASH_VALUE[4]);
-        for block in message.chunks(64) {
-            hash_value = hash_block(hash_value, block);
-        }
-
-        let (h0_3, h4) = hash_value;
-        let h0_3 = vrev32q_u8(vreinterpretq_u8_u32(h0_3));
-
-        let mut digest = [0; 20];
-        vst1q_u8(digest.as_mut_ptr(), h0_3);
-        digest[16..20].copy_from_slice(&h4.to_be_bytes());
-        return digest
+        return hash_message(&message)
     }
 }
 
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    unsafe {
+        *state = compress_blocks(*state, blocks, schedule);
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    unsafe {
+        *state = compress_blocks(*state, blocks, sha0_schedule);
+    }
+}
+
+/// Hashes every message independently.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    return messages.iter().map(|bytes| hash(bytes)).collect()
+}
+
 #[inline]
 fn padding(bytes: &[u8]) -> Vec<u8> {
     let original_length = bytes.len();
-    let zero_count = ((original_length + 64) & !63) - original_length - 9;
+    let zero_count = (119 - original_length % 64) % 64;
     let mut message = bytes.to_owned();
     message.push(0x80);
     message.extend(vec![0; zero_count]);
@@ -48,13 +91,61 @@ fn padding(bytes: &[u8]) -> Vec<u8> {
 }
 
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn hash_message(message: &[u8]) -> [u8; 20] {
+    let mut hash_value = (vld1q_u32(HASH_VALUE.as_ptr()), HASH_VALUE[4]);
+    for block in message.chunks(64) {
+        hash_value = hash_block(hash_value, block);
+    }
+
+    let (h0_3, h4) = hash_value;
+    let h0_3 = swap_big_endian(vreinterpretq_u8_u32(h0_3));
+
+    let mut digest = [0; 20];
+    vst1q_u8(digest.as_mut_ptr(), h0_3);
+    digest[16..20].copy_from_slice(&h4.to_be_bytes());
+    return digest
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn compress_blocks(state: [u32; 5], blocks: &[[u8; 64]], schedule: unsafe fn(&[u8]) -> [uint32x4_t; 20]) -> [u32; 5] {
+    let mut hash_value = (vld1q_u32(state.as_ptr()), state[4]);
+    for block in blocks {
+        hash_value = rounds(hash_value, schedule(block));
+    }
+
+    let (h0_3, h4) = hash_value;
+    let mut buffer = [0; 4];
+    vst1q_u32(buffer.as_mut_ptr(), h0_3);
+    let [h0, h1, h2, h3] = buffer;
+    return [h0, h1, h2, h3, h4]
+}
+
+/// Converts between the bytes of four 32-bit words and their big-endian order. The ARM backends are
+/// little-endian only, see the check in `lib.rs`.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn swap_big_endian(bytes: uint8x16_t) -> uint8x16_t {
+    return vrev32q_u8(bytes)
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
 unsafe fn hash_block(hash_value: (uint32x4_t, u32), bytes: &[u8]) -> (uint32x4_t, u32) {
+    return rounds(hash_value, schedule(bytes))
+}
+
+/// The 80 words of the message schedule, four per vector.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn schedule(bytes: &[u8]) -> [uint32x4_t; 20] {
     let uint8x16x4_t(q0, q1, q2, q3) = vld1q_u8_x4(bytes.as_ptr());
 
-    let w00_w03 = vreinterpretq_u32_u8(vrev32q_u8(q0));
-    let w04_w07 = vreinterpretq_u32_u8(vrev32q_u8(q1));
-    let w08_w11 = vreinterpretq_u32_u8(vrev32q_u8(q2));
-    let w12_w15 = vreinterpretq_u32_u8(vrev32q_u8(q3));
+    let w00_w03 = vreinterpretq_u32_u8(swap_big_endian(q0));
+    let w04_w07 = vreinterpretq_u32_u8(swap_big_endian(q1));
+    let w08_w11 = vreinterpretq_u32_u8(swap_big_endian(q2));
+    let w12_w15 = vreinterpretq_u32_u8(swap_big_endian(q3));
     let w16_w19 = vsha1su1q_u32(vsha1su0q_u32(w00_w03, w04_w07, w08_w11), w12_w15);
     let w20_w23 = vsha1su1q_u32(vsha1su0q_u32(w04_w07, w08_w11, w12_w15), w16_w19);
     let w24_w27 = vsha1su1q_u32(vsha1su0q_u32(w08_w11, w12_w15, w16_w19), w20_w23);
@@ -71,6 +162,58 @@ unsafe fn hash_block(hash_value: (uint32x4_t, u32), bytes: &[u8]) -> (uint32x4_t
     let w68_w71 = vsha1su1q_u32(vsha1su0q_u32(w52_w55, w56_w59, w60_w63), w64_w67);
     let w72_w75 = vsha1su1q_u32(vsha1su0q_u32(w56_w59, w60_w63, w64_w67), w68_w71);
     let w76_w79 = vsha1su1q_u32(vsha1su0q_u32(w60_w63, w64_w67, w68_w71), w72_w75);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+/// The SHA-0 message schedule. `vsha1su0q_u32` only XORs and still applies, the rest of the step
+/// is done by `sha0_su1` since `vsha1su1q_u32` rotates.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn sha0_schedule(bytes: &[u8]) -> [uint32x4_t; 20] {
+    let uint8x16x4_t(q0, q1, q2, q3) = vld1q_u8_x4(bytes.as_ptr());
+
+    let w00_w03 = vreinterpretq_u32_u8(swap_big_endian(q0));
+    let w04_w07 = vreinterpretq_u32_u8(swap_big_endian(q1));
+    let w08_w11 = vreinterpretq_u32_u8(swap_big_endian(q2));
+    let w12_w15 = vreinterpretq_u32_u8(swap_big_endian(q3));
+    let w16_w19 = sha0_su1(vsha1su0q_u32(w00_w03, w04_w07, w08_w11), w12_w15);
+    let w20_w23 = sha0_su1(vsha1su0q_u32(w04_w07, w08_w11, w12_w15), w16_w19);
+    let w24_w27 = sha0_su1(vsha1su0q_u32(w08_w11, w12_w15, w16_w19), w20_w23);
+    let w28_w31 = sha0_su1(vsha1su0q_u32(w12_w15, w16_w19, w20_w23), w24_w27);
+    let w32_w35 = sha0_su1(vsha1su0q_u32(w16_w19, w20_w23, w24_w27), w28_w31);
+    let w36_w39 = sha0_su1(vsha1su0q_u32(w20_w23, w24_w27, w28_w31), w32_w35);
+    let w40_w43 = sha0_su1(vsha1su0q_u32(w24_w27, w28_w31, w32_w35), w36_w39);
+    let w44_w47 = sha0_su1(vsha1su0q_u32(w28_w31, w32_w35, w36_w39), w40_w43);
+    let w48_w51 = sha0_su1(vsha1su0q_u32(w32_w35, w36_w39, w40_w43), w44_w47);
+    let w52_w55 = sha0_su1(vsha1su0q_u32(w36_w39, w40_w43, w44_w47), w48_w51);
+    let w56_w59 = sha0_su1(vsha1su0q_u32(w40_w43, w44_w47, w48_w51), w52_w55);
+    let w60_w63 = sha0_su1(vsha1su0q_u32(w44_w47, w48_w51, w52_w55), w56_w59);
+    let w64_w67 = sha0_su1(vsha1su0q_u32(w48_w51, w52_w55, w56_w59), w60_w63);
+    let w68_w71 = sha0_su1(vsha1su0q_u32(w52_w55, w56_w59, w60_w63), w64_w67);
+    let w72_w75 = sha0_su1(vsha1su0q_u32(w56_w59, w60_w63, w64_w67), w68_w71);
+    let w76_w79 = sha0_su1(vsha1su0q_u32(w60_w63, w64_w67, w68_w71), w72_w75);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn rounds(hash_value: (uint32x4_t, u32), w: [uint32x4_t; 20]) -> (uint32x4_t, u32) {
+    let [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ] = w;
 
     let abcde = hash_value;
     // rounds 1-20
@@ -110,7 +253,25 @@ unsafe fn hash_block(hash_value: (uint32x4_t, u32), bytes: &[u8]) -> (uint32x4_t
     return (vaddq_u32(abcd, h0_3), e.wrapping_add(h4))
 }
 
+/// `vsha1su1q_u32` without the rotation.
+///
+/// |     if 16 <= t <= 79     |
+/// |:------------------------:|
+/// |w16 = w13 ^ w8  ^ w2 ^ w0 |
+/// |w17 = w14 ^ w9  ^ w3 ^ w1 |
+/// |w18 = w15 ^ w10 ^ w4 ^ w2 |
+/// |w19 = w16 ^ w11 ^ w5 ^ w3 |
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
+unsafe fn sha0_su1(sum: uint32x4_t, w12_15: uint32x4_t) -> uint32x4_t {
+    let w16_18 = veorq_u32(sum, vextq_u32::<1>(w12_15, vdupq_n_u32(0)));
+    let w16 = vextq_u32::<1>(vdupq_n_u32(0), w16_18);
+    let w16_19 = veorq_u32(w16_18, w16);
+    return w16_19
+}
+
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon,v8,sha2"))]
 unsafe fn compute(abcde: (uint32x4_t, u32), wx4: uint32x4_t, kx4: uint32x4_t, function: unsafe fn (uint32x4_t, u32, uint32x4_t) -> uint32x4_t) -> (uint32x4_t, u32) {
     let (abcd, e) = abcde;
     let tmp = vsha1h_u32(vgetq_lane_u32(abcd, 0));
@@ -0,0 +1,154 @@
+use std::sync::OnceLock;
+
+use crate::{sha1_arm, sha1_neon, sha1_scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Backend {
+    /// ARMv8 AArch32 SHA1 instructions.
+    Crypto,
+    /// ARMv7 Advanced SIMD.
+    Neon,
+    Scalar,
+}
+
+/// # Examples
+///
+/// ```
+/// let data = "The quick brown fox jumps over the lazy dog".as_bytes();
+/// let expect = [
+///     0x2F, 0xD4, 0xE1, 0xC6, 0x7A, 0x2D, 0x28, 0xFC, 0xED, 0x84,
+///     0x9E, 0xE1, 0xBB, 0x76, 0xE7, 0x39, 0x1B, 0x93, 0xEB, 0x12
+/// ];
+///
+/// let digest = simd_sha1::hash(&data);
+///
+/// assert_eq!(digest, expect);
+/// ```
+pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    return match backend() {
+        Backend::Crypto => sha1_arm::hash(bytes),
+        Backend::Neon => sha1_neon::hash(bytes),
+        Backend::Scalar => sha1_scalar::hash(bytes),
+    }
+}
+
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    match backend() {
+        Backend::Crypto => sha1_arm::compress(state, blocks),
+        Backend::Neon => sha1_neon::compress(state, blocks),
+        Backend::Scalar => sha1_scalar::compress(state, blocks),
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    match backend() {
+        Backend::Crypto => sha1_arm::compress_sha0(state, blocks),
+        Backend::Neon => sha1_neon::compress_sha0(state, blocks),
+        Backend::Scalar => sha1_scalar::compress_sha0(state, blocks),
+    }
+}
+
+/// Hashes every message independently, with the backend the CPU supports.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    return match backend() {
+        Backend::Crypto => sha1_arm::hash_many(messages),
+        Backend::Neon => sha1_neon::hash_many(messages),
+        Backend::Scalar => sha1_scalar::hash_many(messages),
+    }
+}
+
+/// Picks the backend from the hwcaps the kernel reports, the same bits `/proc/cpuinfo` lists as
+/// `neon` and `sha1`. The NEON and crypto backends are only ever entered after this check.
+fn backend() -> Backend {
+    static BACKEND: OnceLock<Backend> = OnceLock::new();
+    return *BACKEND.get_or_init(|| {
+        return match hwcaps() {
+            (true, true) => Backend::Crypto,
+            (true, false) => Backend::Neon,
+            _ => Backend::Scalar,
+        }
+    })
+}
+
+/// Whether the CPU has NEON and the SHA1 instructions.
+fn hwcaps() -> (bool, bool) {
+    const AT_HWCAP: std::ffi::c_ulong = 16;
+    const AT_HWCAP2: std::ffi::c_ulong = 26;
+    const HWCAP_NEON: std::ffi::c_ulong = 1 << 12;
+    const HWCAP2_SHA1: std::ffi::c_ulong = 1 << 2;
+    extern "C" {
+        fn getauxval(kind: std::ffi::c_ulong) -> std::ffi::c_ulong;
+    }
+    let (hwcap, hwcap2) = unsafe { (getauxval(AT_HWCAP), getauxval(AT_HWCAP2)) };
+    return (hwcap & HWCAP_NEON != 0, hwcap2 & HWCAP2_SHA1 != 0)
+}
+
+/// Every backend the CPU supports, not only the one `backend` picks, checked against the scalar one.
+#[cfg(test)]
+mod tests {
+    use crate::{sha1_arm, sha1_neon, sha1_scalar};
+
+    #[test]
+    fn supported_backends_match_scalar() {
+        let (neon, sha1) = super::hwcaps();
+        if neon {
+            check("neon", sha1_neon::hash, sha1_neon::hash_many, sha1_neon::compress);
+        }
+        if neon && sha1 {
+            check("crypto", sha1_arm::hash, sha1_arm::hash_many, sha1_arm::compress);
+        }
+    }
+
+    fn check(name: &str, hash: fn(&[u8]) -> [u8; 20], hash_many: fn(&[&[u8]]) -> Vec<[u8; 20]>, compress: fn(&mut [u32; 5], &[[u8; 64]])) {
+        let messages: Vec<Vec<u8>> = (0..300).map(|length| (0..length).map(|byte| (byte * 3 + length) as u8).collect()).collect();
+        let messages: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+        let expect = sha1_scalar::hash_many(&messages);
+        assert_eq!(hash_many(&messages), expect, "{name}");
+        for (message, expect) in messages.iter().zip(&expect) {
+            assert_eq!(hash(message), *expect, "{name}, {} bytes", message.len());
+        }
+
+        let blocks: Vec<[u8; 64]> = (0..5).map(|block| std::array::from_fn(|byte| (block * 64 + byte) as u8)).collect();
+        let (mut state, mut expect) = (crate::IV, crate::IV);
+        compress(&mut state, &blocks);
+        sha1_scalar::compress(&mut expect, &blocks);
+        assert_eq!(state, expect, "{name}");
+    }
+}
@@ -0,0 +1,328 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use crate::sha1_sse::hash_block;
+
+const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// # Examples
+///
+/// ```
+/// let data = "The quick brown fox jumps over the lazy dog".as_bytes();
+/// let expect = [
+///     0x2F, 0xD4, 0xE1, 0xC6, 0x7A, 0x2D, 0x28, 0xFC, 0xED, 0x84,
+///     0x9E, 0xE1, 0xBB, 0x76, 0xE7, 0x39, 0x1B, 0x93, 0xEB, 0x12
+/// ];
+///
+/// let digest = simd_sha1::hash(&data);
+///
+/// assert_eq!(digest, expect);
+/// ```
+pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let message = padding(bytes);
+    let hash_value = unsafe { hash_blocks(HASH_VALUE, &message.0) };
+
+    let [h0, h1, h2, h3, h4] = hash_value;
+
+    let mut digest = [0; 20];
+    digest[00..04].copy_from_slice(&h0.to_be_bytes());
+    digest[04..08].copy_from_slice(&h1.to_be_bytes());
+    digest[08..12].copy_from_slice(&h2.to_be_bytes());
+    digest[12..16].copy_from_slice(&h3.to_be_bytes());
+    digest[16..20].copy_from_slice(&h4.to_be_bytes());
+    return digest
+}
+
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    unsafe {
+        *state = hash_blocks(*state, blocks.as_flattened());
+    }
+}
+
+#[repr(align(16))]
+struct Align16<T>(T);
+
+#[repr(align(32))]
+struct Align32<T>(T);
+
+#[inline]
+fn padding(bytes: &[u8]) -> Align16<Vec<u8>> {
+    let original_length = bytes.len();
+    let zero_count = (119 - original_length % 64) % 64;
+    let mut message = bytes.to_owned();
+    message.push(0x80);
+    message.extend(vec![0; zero_count]);
+    message.extend(((original_length as u64) << 3).to_be_bytes());
+    return Align16(message)
+}
+
+/// Runs whole 64-byte blocks, two at a time, with an odd last block going through the SSE backend.
+#[inline]
+unsafe fn hash_blocks(mut hash_value: [u32; 5], message: &[u8]) -> [u32; 5] {
+    let mut pairs = message.chunks_exact(128);
+    let mut wk = Align32([0u32; 160]);
+    let mut next_wk = Align32([0u32; 160]);
+    let mut pair = pairs.next();
+    if let Some(bytes) = pair {
+        schedule(bytes, &mut wk);
+    }
+    while pair.is_some() {
+        pair = pairs.next();
+        hash_value = hash_pair(hash_value, &wk, pair, &mut next_wk);
+        std::mem::swap(&mut wk, &mut next_wk);
+    }
+    if !pairs.remainder().is_empty() {
+        hash_value = hash_block(hash_value, pairs.remainder());
+    }
+    return hash_value
+}
+
+/// Runs the rounds of two consecutive blocks whose W+K were stored in `wk` by the previous call
+/// (or by `schedule`), while the message schedule of the `next` two blocks is computed into
+/// `next_wk` in between the rounds of the first block.
+///
+/// `wk` holds four words of the first block followed by the same four words of the second block.
+#[inline]
+unsafe fn hash_pair(hash_value: [u32; 5], wk: &Align32<[u32; 160]>, next: Option<&[u8]>, next_wk: &mut Align32<[u32; 160]>) -> [u32; 5] {
+    let mut w = [_mm256_setzero_si256(); 20];
+    if let Some(bytes) = next {
+        [w[0], w[1], w[2], w[3]] = schedule_v0(bytes);
+    }
+
+    let has_next = next.is_some();
+    let abcde = hash_value;
+    // rounds 1-20
+    let abcde = interleave::<00>(abcde, wk, &mut w, has_next, next_wk, choose);
+    let abcde = interleave::<01>(abcde, wk, &mut w, has_next, next_wk, choose);
+    let abcde = interleave::<02>(abcde, wk, &mut w, has_next, next_wk, choose);
+    let abcde = interleave::<03>(abcde, wk, &mut w, has_next, next_wk, choose);
+    let abcde = interleave::<04>(abcde, wk, &mut w, has_next, next_wk, choose);
+
+    // rounds 21-40
+    let abcde = interleave::<05>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<06>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<07>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<08>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<09>(abcde, wk, &mut w, has_next, next_wk, parity);
+
+    // rounds 41-60
+    let abcde = interleave::<10>(abcde, wk, &mut w, has_next, next_wk, majority);
+    let abcde = interleave::<11>(abcde, wk, &mut w, has_next, next_wk, majority);
+    let abcde = interleave::<12>(abcde, wk, &mut w, has_next, next_wk, majority);
+    let abcde = interleave::<13>(abcde, wk, &mut w, has_next, next_wk, majority);
+    let abcde = interleave::<14>(abcde, wk, &mut w, has_next, next_wk, majority);
+
+    // rounds 61-80
+    let abcde = interleave::<15>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<16>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<17>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<18>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let abcde = interleave::<19>(abcde, wk, &mut w, has_next, next_wk, parity);
+    let hash_value = add(hash_value, abcde);
+
+    let abcde = hash_value;
+    // rounds 1-20
+    let abcde = compute(abcde, wk, 004, choose);
+    let abcde = compute(abcde, wk, 012, choose);
+    let abcde = compute(abcde, wk, 020, choose);
+    let abcde = compute(abcde, wk, 028, choose);
+    let abcde = compute(abcde, wk, 036, choose);
+
+    // rounds 21-40
+    let abcde = compute(abcde, wk, 044, parity);
+    let abcde = compute(abcde, wk, 052, parity);
+    let abcde = compute(abcde, wk, 060, parity);
+    let abcde = compute(abcde, wk, 068, parity);
+    let abcde = compute(abcde, wk, 076, parity);
+
+    // rounds 41-60
+    let abcde = compute(abcde, wk, 084, majority);
+    let abcde = compute(abcde, wk, 092, majority);
+    let abcde = compute(abcde, wk, 100, majority);
+    let abcde = compute(abcde, wk, 108, majority);
+    let abcde = compute(abcde, wk, 116, majority);
+
+    // rounds 61-80
+    let abcde = compute(abcde, wk, 124, parity);
+    let abcde = compute(abcde, wk, 132, parity);
+    let abcde = compute(abcde, wk, 140, parity);
+    let abcde = compute(abcde, wk, 148, parity);
+    let abcde = compute(abcde, wk, 156, parity);
+
+    return add(hash_value, abcde)
+}
+
+/// Computes `w[4S..4S+4]` of the next two blocks if there are any, then runs rounds `4S+1..=4S+4`
+/// of the first block.
+#[inline(always)]
+unsafe fn interleave<const S: usize>(abcde: [u32; 5], wk: &Align32<[u32; 160]>, w: &mut [__m256i; 20], has_next: bool, next_wk: &mut Align32<[u32; 160]>, function: fn(u32, u32, u32) -> u32) -> [u32; 5] {
+    if has_next {
+        schedule_step(w, S);
+        store(next_wk, S, w[S]);
+    }
+    return compute(abcde, wk, S * 8, function)
+}
+
+/// Computes the W+K of two consecutive blocks without running any rounds.
+#[inline]
+unsafe fn schedule(bytes: &[u8], wk: &mut Align32<[u32; 160]>) {
+    let mut w = [_mm256_setzero_si256(); 20];
+    [w[0], w[1], w[2], w[3]] = schedule_v0(bytes);
+    for s in 0..20 {
+        schedule_step(&mut w, s);
+        store(wk, s, w[s]);
+    }
+}
+
+/// Computes `w[4s..4s+4]` of both blocks, the first four are loaded by `schedule_v0`.
+#[inline(always)]
+unsafe fn schedule_step(w: &mut [__m256i; 20], s: usize) {
+    w[s] = match s {
+        0..=3 => w[s],
+        4..=7 => schedule_v1(w[s - 4], w[s - 3], w[s - 2], w[s - 1]),
+        8..=15 => schedule_v2(w[s - 8], w[s - 7], w[s - 4], w[s - 2], w[s - 1]),
+        _ => schedule_v3(w[s - 16], w[s - 14], w[s - 8], w[s - 3]),
+    };
+}
+
+#[inline]
+unsafe fn store(wk: &mut Align32<[u32; 160]>, s: usize, wx8: __m256i) {
+    let kx8 = _mm256_set1_epi32([0x5A827999u32, 0x6ED9EBA1, 0x8F1BBCDC, 0xCA62C1D6][s / 5] as i32);
+    _mm256_store_si256(wk.0.as_mut_ptr().add(s * 8).cast(), _mm256_add_epi32(wx8, kx8));
+}
+
+#[inline]
+fn add(hash_value: [u32; 5], abcde: [u32; 5]) -> [u32; 5] {
+    let [a, b, c, d, e] = abcde;
+    let [h0, h1, h2, h3, h4] = hash_value;
+    return [
+        a.wrapping_add(h0),
+        b.wrapping_add(h1),
+        c.wrapping_add(h2),
+        d.wrapping_add(h3),
+        e.wrapping_add(h4),
+    ]
+}
+
+/// |if 0 <= t <= 15|
+/// |:-------------:|
+/// |    w0 = m0    |
+/// |    w1 = m1    |
+/// |    w2 = m2    |
+/// |    w3 = m3    |
+///
+/// The first block goes to the low 128 bits, the second block to the high 128 bits.
+#[inline]
+unsafe fn schedule_v0(bytes: &[u8]) -> [__m256i; 4] {
+    let mask = _mm256_set_epi64x(0x0c0d0e0f08090a0b, 0x0405060700010203, 0x0c0d0e0f08090a0b, 0x0405060700010203);
+    let pointer = bytes.as_ptr().cast::<__m128i>();
+    let q0 = _mm256_loadu2_m128i(pointer.add(4), pointer);
+    let q1 = _mm256_loadu2_m128i(pointer.add(5), pointer.add(1));
+    let q2 = _mm256_loadu2_m128i(pointer.add(6), pointer.add(2));
+    let q3 = _mm256_loadu2_m128i(pointer.add(7), pointer.add(3));
+    return [
+        _mm256_shuffle_epi8(q0, mask),
+        _mm256_shuffle_epi8(q1, mask),
+        _mm256_shuffle_epi8(q2, mask),
+        _mm256_shuffle_epi8(q3, mask)
+    ]
+}
+
+/// |       if 16 <= t <= 79          |
+/// |:-------------------------------:|
+/// |w16 = (w13 ^ w8  ^ w2 ^ w0) <<< 1|
+/// |w17 = (w14 ^ w9  ^ w3 ^ w1) <<< 1|
+/// |w18 = (w15 ^ w10 ^ w4 ^ w2) <<< 1|
+/// |w19 = (w16 ^ w11 ^ w5 ^ w3) <<< 1|
+#[inline]
+unsafe fn schedule_v1(w0_3: __m256i, w4_7: __m256i, w8_11: __m256i, w12_15: __m256i) -> __m256i {
+    let w13_15 = _mm256_srli_si256::<4>(w12_15);
+    let w2_5 = _mm256_alignr_epi8::<8>(w4_7, w0_3);
+    let sum = _mm256_xor_si256(_mm256_xor_si256(w13_15, w8_11), _mm256_xor_si256(w2_5, w0_3));
+    let w16_18 = _mm256_xor_si256(_mm256_srli_epi32::<31>(sum), _mm256_slli_epi32::<1>(sum));
+    let w16 = _mm256_slli_si256::<12>(w16_18);
+    let w16rol1 = _mm256_xor_si256(_mm256_srli_epi32::<31>(w16), _mm256_slli_epi32::<1>(w16));
+    let w16_19 = _mm256_xor_si256(w16_18, w16rol1);
+    return w16_19
+}
+
+/// |         if 32 <= t <= 79        |
+/// |:-------------------------------:|
+/// |w32 = (w26 ^ w16 ^ w4 ^ w0) <<< 2|
+/// |w33 = (w27 ^ w17 ^ w5 ^ w1) <<< 2|
+/// |w34 = (w28 ^ w18 ^ w6 ^ w2) <<< 2|
+/// |w35 = (w29 ^ w19 ^ w7 ^ w3) <<< 2|
+#[inline]
+unsafe fn schedule_v2(w0_3: __m256i, w4_7: __m256i, w16_19: __m256i, w24_27: __m256i, w28_31: __m256i) -> __m256i {
+    let w26_29 = _mm256_alignr_epi8::<8>(w28_31, w24_27);
+    let sum = _mm256_xor_si256(_mm256_xor_si256(w26_29, w16_19), _mm256_xor_si256(w4_7, w0_3));
+    let w32_35 = _mm256_xor_si256(_mm256_srli_epi32::<30>(sum), _mm256_slli_epi32::<2>(sum));
+    return w32_35
+}
+
+/// |         if 64 <= t <= 79         |
+/// |:--------------------------------:|
+/// |w64 = (w52 ^ w32 ^ w8  ^ w0) <<< 4|
+/// |w65 = (w53 ^ w33 ^ w9  ^ w1) <<< 4|
+/// |w66 = (w54 ^ w34 ^ w10 ^ w2) <<< 4|
+/// |w67 = (w55 ^ w35 ^ w11 ^ w3) <<< 4|
+#[inline]
+unsafe fn schedule_v3(w0_3: __m256i, w8_11: __m256i, w32_35: __m256i, w52_55: __m256i) -> __m256i {
+    let sum = _mm256_xor_si256(_mm256_xor_si256(w52_55, w32_35), _mm256_xor_si256(w8_11, w0_3));
+    let w64_67 = _mm256_xor_si256(_mm256_srli_epi32::<28>(sum), _mm256_slli_epi32::<4>(sum));
+    return w64_67
+}
+
+/// Runs four rounds with the W+K stored at `wk[offset..offset + 4]`.
+#[inline]
+fn compute(abcde: [u32; 5], wk: &Align32<[u32; 160]>, offset: usize, function: fn(u32, u32, u32) -> u32) -> [u32; 5] {
+    let [mut a, mut b, mut c, mut d, mut e] = abcde;
+
+    for &wk in &wk.0[offset..offset + 4] {
+        let tmp = e.wrapping_add(a.rotate_left(5)).wrapping_add(function(b, c, d)).wrapping_add(wk);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = tmp;
+    }
+
+    return [a, b, c, d, e]
+}
+
+#[inline]
+fn choose(b: u32, c: u32, d: u32) -> u32 {
+    return b & c ^ !b & d
+}
+
+#[inline]
+fn majority(b: u32, c: u32, d: u32) -> u32 {
+    return b & c ^ b & d ^ c & d
+}
+
+#[inline]
+fn parity(b: u32, c: u32, d: u32) -> u32 {
+    return b ^ c ^ d
+}
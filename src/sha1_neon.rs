@@ -19,6 +19,8 @@ const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0x
 /// assert_eq!(digest, expect);
 /// ```
 pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
     let message = padding(bytes);
     let mut hash_value = HASH_VALUE;
     for block in message.chunks(64) {
@@ -38,10 +40,84 @@ pub fn hash(bytes: &[u8]) -> [u8; 20] {
     return digest
 }
 
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        unsafe {
+            *state = hash_block(*state, block);
+        }
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        unsafe {
+            *state = hash_block_sha0(*state, block);
+        }
+    }
+}
+
+/// Hashes every message independently, four at a time in the lanes of `uint32x4_t`.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let mut digests = Vec::with_capacity(messages.len());
+    for group in messages.chunks(4) {
+        let mut lanes: [Vec<u8>; 4] = Default::default();
+        for (lane, bytes) in lanes.iter_mut().zip(group) {
+            *lane = padding(bytes);
+        }
+
+        let hash_values = unsafe { hash_lanes(&lanes) };
+        for [h0, h1, h2, h3, h4] in &hash_values[..group.len()] {
+            let mut digest = [0; 20];
+            digest[00..04].copy_from_slice(&h0.to_be_bytes());
+            digest[04..08].copy_from_slice(&h1.to_be_bytes());
+            digest[08..12].copy_from_slice(&h2.to_be_bytes());
+            digest[12..16].copy_from_slice(&h3.to_be_bytes());
+            digest[16..20].copy_from_slice(&h4.to_be_bytes());
+            digests.push(digest);
+        }
+    }
+    return digests
+}
+
 #[inline]
 fn padding(bytes: &[u8]) -> Vec<u8> {
     let original_length = bytes.len();
-    let zero_count = ((original_length + 64) & !63) - original_length - 9;
+    let zero_count = (119 - original_length % 64) % 64;
     let mut message = bytes.to_owned();
     message.push(0x80);
     message.extend(vec![0; zero_count]);
@@ -49,14 +125,36 @@ fn padding(bytes: &[u8]) -> Vec<u8> {
     return message
 }
 
+/// Converts between the bytes of four 32-bit words and their big-endian order. The ARM backends are
+/// little-endian only, see the check in `lib.rs`.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn swap_big_endian(bytes: uint8x16_t) -> uint8x16_t {
+    return vrev32q_u8(bytes)
+}
+
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
 unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, schedule(bytes))
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn hash_block_sha0(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, sha0_schedule(bytes))
+}
+
+/// The 80 words of the message schedule, four per vector.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn schedule(bytes: &[u8]) -> [uint32x4_t; 20] {
     let uint8x16x4_t(q0, q1, q2, q3) = vld1q_u8_x4(bytes.as_ptr());
 
-    let w00_w03 = vreinterpretq_u32_u8(vrev32q_u8(q0));
-    let w04_w07 = vreinterpretq_u32_u8(vrev32q_u8(q1));
-    let w08_w11 = vreinterpretq_u32_u8(vrev32q_u8(q2));
-    let w12_w15 = vreinterpretq_u32_u8(vrev32q_u8(q3));
+    let w00_w03 = vreinterpretq_u32_u8(swap_big_endian(q0));
+    let w04_w07 = vreinterpretq_u32_u8(swap_big_endian(q1));
+    let w08_w11 = vreinterpretq_u32_u8(swap_big_endian(q2));
+    let w12_w15 = vreinterpretq_u32_u8(swap_big_endian(q3));
     let w16_w19 = schedule_v1(w00_w03, w04_w07, w08_w11, w12_w15);
     let w20_w23 = schedule_v1(w04_w07, w08_w11, w12_w15, w16_w19);
     let w24_w27 = schedule_v1(w08_w11, w12_w15, w16_w19, w20_w23);
@@ -73,6 +171,57 @@ unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
     let w68_w71 = schedule_v3(w04_w07, w12_w15, w36_w39, w56_w59);
     let w72_w75 = schedule_v3(w08_w11, w16_w19, w40_w43, w60_w63);
     let w76_w79 = schedule_v3(w12_w15, w20_w23, w44_w47, w64_w67);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+/// The SHA-0 message schedule, which has no rotation in the expansion.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn sha0_schedule(bytes: &[u8]) -> [uint32x4_t; 20] {
+    let uint8x16x4_t(q0, q1, q2, q3) = vld1q_u8_x4(bytes.as_ptr());
+
+    let w00_w03 = vreinterpretq_u32_u8(swap_big_endian(q0));
+    let w04_w07 = vreinterpretq_u32_u8(swap_big_endian(q1));
+    let w08_w11 = vreinterpretq_u32_u8(swap_big_endian(q2));
+    let w12_w15 = vreinterpretq_u32_u8(swap_big_endian(q3));
+    let w16_w19 = sha0_schedule_v1(w00_w03, w04_w07, w08_w11, w12_w15);
+    let w20_w23 = sha0_schedule_v1(w04_w07, w08_w11, w12_w15, w16_w19);
+    let w24_w27 = sha0_schedule_v1(w08_w11, w12_w15, w16_w19, w20_w23);
+    let w28_w31 = sha0_schedule_v1(w12_w15, w16_w19, w20_w23, w24_w27);
+    let w32_w35 = sha0_schedule_v2(w00_w03, w04_w07, w16_w19, w24_w27, w28_w31);
+    let w36_w39 = sha0_schedule_v2(w04_w07, w08_w11, w20_w23, w28_w31, w32_w35);
+    let w40_w43 = sha0_schedule_v2(w08_w11, w12_w15, w24_w27, w32_w35, w36_w39);
+    let w44_w47 = sha0_schedule_v2(w12_w15, w16_w19, w28_w31, w36_w39, w40_w43);
+    let w48_w51 = sha0_schedule_v2(w16_w19, w20_w23, w32_w35, w40_w43, w44_w47);
+    let w52_w55 = sha0_schedule_v2(w20_w23, w24_w27, w36_w39, w44_w47, w48_w51);
+    let w56_w59 = sha0_schedule_v2(w24_w27, w28_w31, w40_w43, w48_w51, w52_w55);
+    let w60_w63 = sha0_schedule_v2(w28_w31, w32_w35, w44_w47, w52_w55, w56_w59);
+    let w64_w67 = sha0_schedule_v3(w00_w03, w08_w11, w32_w35, w52_w55);
+    let w68_w71 = sha0_schedule_v3(w04_w07, w12_w15, w36_w39, w56_w59);
+    let w72_w75 = sha0_schedule_v3(w08_w11, w16_w19, w40_w43, w60_w63);
+    let w76_w79 = sha0_schedule_v3(w12_w15, w20_w23, w44_w47, w64_w67);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn rounds(hash_value: [u32; 5], w: [uint32x4_t; 20]) -> [u32; 5] {
+    let [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ] = w;
 
     let abcde = hash_value;
     // 1-20
@@ -125,6 +274,7 @@ unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
 /// |w18 = (w15 ^ w10 ^ w4 ^ w2) <<< 1|
 /// |w19 = (w16 ^ w11 ^ w5 ^ w3) <<< 1|
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
 unsafe fn schedule_v1(w0_3: uint32x4_t, w4_7: uint32x4_t, w8_11: uint32x4_t, w12_15: uint32x4_t) -> uint32x4_t {
     let w13_15 = vextq_u32::<1>(w12_15, vdupq_n_u32(0));
     let w2_5 = vextq_u32::<2>(w0_3, w4_7);
@@ -143,6 +293,7 @@ unsafe fn schedule_v1(w0_3: uint32x4_t, w4_7: uint32x4_t, w8_11: uint32x4_t, w12
 /// |w34 = (w28 ^ w18 ^ w6 ^ w2) <<< 2|
 /// |w35 = (w29 ^ w19 ^ w7 ^ w3) <<< 2|
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
 unsafe fn schedule_v2(w0_3: uint32x4_t, w4_7: uint32x4_t, w16_19: uint32x4_t, w24_27: uint32x4_t, w28_31: uint32x4_t) -> uint32x4_t {
     let w26_29 = vextq_u32::<2>(w24_27, w28_31);
     let sum = veorq_u32(veorq_u32(w26_29, w16_19), veorq_u32(w4_7, w0_3));
@@ -157,13 +308,59 @@ unsafe fn schedule_v2(w0_3: uint32x4_t, w4_7: uint32x4_t, w16_19: uint32x4_t, w2
 /// |w66 = (w54 ^ w34 ^ w10 ^ w2) <<< 4|
 /// |w67 = (w55 ^ w35 ^ w11 ^ w3) <<< 4|
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
 unsafe fn schedule_v3(w0_3: uint32x4_t, w8_11: uint32x4_t, w32_35: uint32x4_t, w52_55: uint32x4_t) -> uint32x4_t {
     let sum = veorq_u32(veorq_u32(w52_55, w32_35), veorq_u32(w8_11, w0_3));
     let w64_67 = veorq_u32(vshrq_n_u32::<28>(sum), vshlq_n_u32::<4>(sum));
     return w64_67
 }
 
+/// |     if 16 <= t <= 79     |
+/// |:------------------------:|
+/// |w16 = w13 ^ w8  ^ w2 ^ w0 |
+/// |w17 = w14 ^ w9  ^ w3 ^ w1 |
+/// |w18 = w15 ^ w10 ^ w4 ^ w2 |
+/// |w19 = w16 ^ w11 ^ w5 ^ w3 |
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn sha0_schedule_v1(w0_3: uint32x4_t, w4_7: uint32x4_t, w8_11: uint32x4_t, w12_15: uint32x4_t) -> uint32x4_t {
+    let w13_15 = vextq_u32::<1>(w12_15, vdupq_n_u32(0));
+    let w2_5 = vextq_u32::<2>(w0_3, w4_7);
+    let w16_18 = veorq_u32(veorq_u32(w13_15, w8_11), veorq_u32(w2_5, w0_3));
+    let w16 = vextq_u32::<1>(vdupq_n_u32(0), w16_18);
+    let w16_19 = veorq_u32(w16_18, w16);
+    return w16_19
+}
+
+/// |     if 32 <= t <= 79     |
+/// |:------------------------:|
+/// |w32 = w26 ^ w16 ^ w4 ^ w0 |
+/// |w33 = w27 ^ w17 ^ w5 ^ w1 |
+/// |w34 = w28 ^ w18 ^ w6 ^ w2 |
+/// |w35 = w29 ^ w19 ^ w7 ^ w3 |
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn sha0_schedule_v2(w0_3: uint32x4_t, w4_7: uint32x4_t, w16_19: uint32x4_t, w24_27: uint32x4_t, w28_31: uint32x4_t) -> uint32x4_t {
+    let w26_29 = vextq_u32::<2>(w24_27, w28_31);
+    let w32_35 = veorq_u32(veorq_u32(w26_29, w16_19), veorq_u32(w4_7, w0_3));
+    return w32_35
+}
+
+/// |     if 64 <= t <= 79      |
+/// |:-------------------------:|
+/// |w64 = w52 ^ w32 ^ w8  ^ w0 |
+/// |w65 = w53 ^ w33 ^ w9  ^ w1 |
+/// |w66 = w54 ^ w34 ^ w10 ^ w2 |
+/// |w67 = w55 ^ w35 ^ w11 ^ w3 |
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn sha0_schedule_v3(w0_3: uint32x4_t, w8_11: uint32x4_t, w32_35: uint32x4_t, w52_55: uint32x4_t) -> uint32x4_t {
+    let w64_67 = veorq_u32(veorq_u32(w52_55, w32_35), veorq_u32(w8_11, w0_3));
+    return w64_67
+}
+
 #[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
 unsafe fn compute(abcde: [u32; 5], wx4: uint32x4_t, kx4: uint32x4_t, func: fn(u32, u32, u32) -> u32) -> [u32; 5] {
     let mut wkx4 = [0u32; 4];
     vst1q_u32(wkx4.as_mut_ptr(), vaddq_u32(wx4, kx4));
@@ -195,4 +392,118 @@ fn majority(b: u32, c: u32, d: u32) -> u32 {
 #[inline]
 fn parity(b: u32, c: u32, d: u32) -> u32 {
     return b ^ c ^ d
+}
+
+/// Runs the blocks of four padded messages side by side, one message per lane.
+/// A lane whose message has no more blocks keeps its hash value.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn hash_lanes(lanes: &[Vec<u8>; 4]) -> [[u32; 5]; 4] {
+    let block_count = lanes.iter().map(|lane| lane.len() / 64).max().unwrap_or(0);
+    let empty = [0u8; 64];
+    let mut hash_value = HASH_VALUE.map(|h| vdupq_n_u32(h));
+    for index in 0..block_count {
+        let mut active = [0u32; 4];
+        let blocks: [&[u8]; 4] = std::array::from_fn(|lane| match lanes[lane].get(index * 64..index * 64 + 64) {
+            Some(block) => {
+                active[lane] = u32::MAX;
+                block
+            }
+            None => &empty,
+        });
+
+        // four words of every lane per load, transposed into one word of all four lanes per vector
+        let mut w = [vdupq_n_u32(0); 16];
+        for (quarter, w) in w.chunks_mut(4).enumerate() {
+            let rows = blocks.map(|block| vreinterpretq_u32_u8(swap_big_endian(vld1q_u8(block[quarter * 16..].as_ptr()))));
+            w.copy_from_slice(&transpose_x4(rows));
+        }
+        let mask = vld1q_u32(active.as_ptr());
+        let next = hash_block_x4(hash_value, w);
+        for (h, n) in hash_value.iter_mut().zip(next) {
+            *h = vbslq_u32(mask, n, *h);
+        }
+    }
+
+    let mut buffer = [[0u32; 4]; 5];
+    for (buffer, h) in buffer.iter_mut().zip(hash_value) {
+        vst1q_u32(buffer.as_mut_ptr(), h);
+    }
+    return std::array::from_fn(|lane| std::array::from_fn(|i| buffer[i][lane]))
+}
+
+/// Transposes a 4x4 matrix of words: lane `j` of vector `i` of the result is lane `i` of `rows[j]`.
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn transpose_x4(rows: [uint32x4_t; 4]) -> [uint32x4_t; 4] {
+    let [r0, r1, r2, r3] = rows;
+    // [r0[0] r1[0] r0[2] r1[2]] and [r0[1] r1[1] r0[3] r1[3]]
+    let uint32x4x2_t(even01, odd01) = vtrnq_u32(r0, r1);
+    let uint32x4x2_t(even23, odd23) = vtrnq_u32(r2, r3);
+    return [
+        vcombine_u32(vget_low_u32(even01), vget_low_u32(even23)),
+        vcombine_u32(vget_low_u32(odd01), vget_low_u32(odd23)),
+        vcombine_u32(vget_high_u32(even01), vget_high_u32(even23)),
+        vcombine_u32(vget_high_u32(odd01), vget_high_u32(odd23)),
+    ]
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn hash_block_x4(hash_value: [uint32x4_t; 5], mut w: [uint32x4_t; 16]) -> [uint32x4_t; 5] {
+    let mut abcde = hash_value;
+    for t in 0..80 {
+        if t >= 16 {
+            let sum = veorq_u32(
+                veorq_u32(w[(t - 3) & 15], w[(t - 8) & 15]),
+                veorq_u32(w[(t - 14) & 15], w[t & 15])
+            );
+            w[t & 15] = rotate_left_x4::<1, 31>(sum);
+        }
+        abcde = match t / 20 {
+            0 => compute_x4(abcde, w[t & 15], 0x5A827999, choose_x4),
+            1 => compute_x4(abcde, w[t & 15], 0x6ED9EBA1, parity_x4),
+            2 => compute_x4(abcde, w[t & 15], 0x8F1BBCDC, majority_x4),
+            _ => compute_x4(abcde, w[t & 15], 0xCA62C1D6, parity_x4),
+        };
+    }
+
+    let mut hash_value = hash_value;
+    for (h, x) in hash_value.iter_mut().zip(abcde) {
+        *h = vaddq_u32(*h, x);
+    }
+    return hash_value
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn compute_x4(abcde: [uint32x4_t; 5], w: uint32x4_t, k: u32, function: unsafe fn(uint32x4_t, uint32x4_t, uint32x4_t) -> uint32x4_t) -> [uint32x4_t; 5] {
+    let [a, b, c, d, e] = abcde;
+    let wk = vaddq_u32(w, vdupq_n_u32(k));
+    let tmp = vaddq_u32(vaddq_u32(e, rotate_left_x4::<5, 27>(a)), vaddq_u32(function(b, c, d), wk));
+    return [tmp, a, rotate_left_x4::<30, 2>(b), c, d]
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn rotate_left_x4<const L: i32, const R: i32>(x: uint32x4_t) -> uint32x4_t {
+    return vsliq_n_u32::<L>(vshrq_n_u32::<R>(x), x)
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn choose_x4(b: uint32x4_t, c: uint32x4_t, d: uint32x4_t) -> uint32x4_t {
+    return vbslq_u32(b, c, d)
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn majority_x4(b: uint32x4_t, c: uint32x4_t, d: uint32x4_t) -> uint32x4_t {
+    return vbslq_u32(veorq_u32(b, c), d, b)
+}
+
+#[inline]
+#[cfg_attr(target_arch = "arm", target_feature(enable = "neon"))]
+unsafe fn parity_x4(b: uint32x4_t, c: uint32x4_t, d: uint32x4_t) -> uint32x4_t {
+    return veorq_u32(veorq_u32(b, c), d)
 }
\ No newline at end of file
@@ -0,0 +1,333 @@
+use std::arch::asm;
+use std::sync::OnceLock;
+
+use crate::sha1_scalar;
+
+const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// Hashes every message independently.
+///
+/// When the V extension is reported by the kernel, VLEN/32 messages are hashed at a time, one
+/// message per 32-bit element of a vector register. Otherwise every message goes through the
+/// scalar backend.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    if !has_vector() {
+        return sha1_scalar::hash_many(messages)
+    }
+
+    let lanes = unsafe { vlmax() };
+    let mut digests = Vec::with_capacity(messages.len());
+    // allocated once and reused by every group: the padded last one or two blocks of each message,
+    // and the rows `hash_lanes` works on
+    let mut tails = vec![[0; 128]; lanes];
+    let mut state = vec![0; 5 * lanes];
+    let mut w = vec![0; 80 * lanes];
+    let mut active = vec![0; lanes];
+    for group in messages.chunks(lanes) {
+        let count = group.len();
+        for (tail, bytes) in tails.iter_mut().zip(group) {
+            pad_tail(tail, bytes);
+        }
+        unsafe { hash_lanes(group, &tails, &mut state[..5 * count], &mut w[..80 * count], &mut active[..count]) };
+        for lane in 0..count {
+            let mut digest = [0; 20];
+            for (bytes, i) in digest.chunks_mut(4).zip(0..5) {
+                bytes.copy_from_slice(&state[i * count + lane].to_be_bytes());
+            }
+            digests.push(digest);
+        }
+    }
+    return digests
+}
+
+/// Whether the kernel reports the V extension in `AT_HWCAP`.
+fn has_vector() -> bool {
+    static VECTOR: OnceLock<bool> = OnceLock::new();
+    return *VECTOR.get_or_init(|| {
+        #[cfg(target_os = "linux")]
+        {
+            const AT_HWCAP: std::ffi::c_ulong = 16;
+            const HWCAP_V: std::ffi::c_ulong = 1 << (b'V' - b'A');
+            extern "C" {
+                fn getauxval(kind: std::ffi::c_ulong) -> std::ffi::c_ulong;
+            }
+            return unsafe { getauxval(AT_HWCAP) } & HWCAP_V != 0
+        }
+        #[cfg(not(target_os = "linux"))]
+        return false
+    })
+}
+
+/// Pads the bytes of `bytes` after its last full block into one or two blocks at the start of `tail`.
+#[inline]
+fn pad_tail(tail: &mut [u8; 128], bytes: &[u8]) {
+    let rest = &bytes[bytes.len() / 64 * 64..];
+    tail.fill(0);
+    tail[..rest.len()].copy_from_slice(rest);
+    tail[rest.len()] = 0x80;
+    let end = if rest.len() < 56 { 64 } else { 128 };
+    tail[end - 8..end].copy_from_slice(&((bytes.len() as u64) << 3).to_be_bytes());
+}
+
+/// Number of 32-bit elements in a vector register.
+#[inline]
+unsafe fn vlmax() -> usize {
+    let vlmax: usize;
+    asm!(
+        ".option push",
+        ".option arch, +v",
+        "vsetvli {vlmax}, zero, e32, m1, ta, ma",
+        ".option pop",
+        vlmax = out(reg) vlmax,
+        options(nomem, nostack),
+    );
+    return vlmax
+}
+
+/// Hashes up to VLMAX messages side by side, one message per element, leaving row i of `state` with
+/// the i-th hash value word of every lane. Full blocks are read from the messages, the padded ones
+/// from `tails`. A lane whose message has no more blocks is cleared in `active` and keeps its hash
+/// value.
+#[inline]
+unsafe fn hash_lanes(messages: &[&[u8]], tails: &[[u8; 128]], state: &mut [u32], w: &mut [u32], active: &mut [u32]) {
+    static EMPTY: [u8; 64] = [0; 64];
+    let count = messages.len();
+    let block_count = messages.iter().map(|bytes| (bytes.len() + 9).div_ceil(64)).max().unwrap_or(0);
+    for (row, h) in state.chunks_mut(count).zip(HASH_VALUE) {
+        row.fill(h);
+    }
+    for index in 0..block_count {
+        for (lane, (bytes, tail)) in messages.iter().zip(tails).enumerate() {
+            let full = bytes.len() / 64;
+            let blocks = (bytes.len() + 9).div_ceil(64);
+            let block = if index < full {
+                &bytes[index * 64..index * 64 + 64]
+            } else if index < blocks {
+                &tail[(index - full) * 64..(index - full) * 64 + 64]
+            } else {
+                &EMPTY
+            };
+            active[lane] = if index < blocks { u32::MAX } else { 0 };
+            for (t, bytes) in block.chunks(4).enumerate() {
+                w[t * count + lane] = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+        }
+        compress(state, w, active, count);
+    }
+}
+
+/// Compresses one block of each of `lanes` messages.
+///
+/// `state` holds five rows of `lanes` words (a, b, c, d, e), `w` holds 80 rows of `lanes` words of
+/// which the first 16 are the message words. The schedule is expanded into `w` in place, then the
+/// rounds run with a..e in v1..v5. The result is masked with `active` before it is added to the hash
+/// value, so lanes with a zero there keep theirs.
+#[inline]
+unsafe fn compress(state: &mut [u32], w: &mut [u32], active: &[u32], lanes: usize) {
+    debug_assert!(state.len() == 5 * lanes && w.len() == 80 * lanes && active.len() == lanes);
+    let stride = lanes * 4;
+    asm!(
+        ".option push",
+        ".option arch, +v",
+        "vsetvli zero, {lanes}, e32, m1, ta, ma",
+
+        // w[t] = (w[t-3] ^ w[t-8] ^ w[t-14] ^ w[t-16]) <<< 1
+        "mv {p}, {w}",
+        "li {i}, 64",
+        "1:",
+        "vle32.v v8, ({p})",
+        "add {q}, {p}, {stride2}",
+        "vle32.v v9, ({q})",
+        "add {q}, {p}, {stride8}",
+        "vle32.v v10, ({q})",
+        "add {q}, {p}, {stride13}",
+        "vle32.v v11, ({q})",
+        "vxor.vv v8, v8, v9",
+        "vxor.vv v10, v10, v11",
+        "vxor.vv v8, v8, v10",
+        "vsll.vi v9, v8, 1",
+        "vsrl.vi v8, v8, 31",
+        "vor.vv v8, v8, v9",
+        "add {q}, {p}, {stride16}",
+        "vse32.v v8, ({q})",
+        "add {p}, {p}, {stride}",
+        "addi {i}, {i}, -1",
+        "bnez {i}, 1b",
+
+        // a..e to v1..v5, and a copy to v11..v15
+        "mv {q}, {state}",
+        "vle32.v v1, ({q})",
+        "add {q}, {q}, {stride}",
+        "vle32.v v2, ({q})",
+        "add {q}, {q}, {stride}",
+        "vle32.v v3, ({q})",
+        "add {q}, {q}, {stride}",
+        "vle32.v v4, ({q})",
+        "add {q}, {q}, {stride}",
+        "vle32.v v5, ({q})",
+        "vmv.v.v v11, v1",
+        "vmv.v.v v12, v2",
+        "vmv.v.v v13, v3",
+        "vmv.v.v v14, v4",
+        "vmv.v.v v15, v5",
+        "mv {p}, {w}",
+
+        // rounds 1-20, f = d ^ (b & (c ^ d))
+        "li {k}, 0x5A827999",
+        "li {i}, 20",
+        "2:",
+        "vle32.v v8, ({p})",
+        "vxor.vv v9, v3, v4",
+        "vand.vv v9, v9, v2",
+        "vxor.vv v9, v9, v4",
+        "vsll.vi v10, v1, 5",
+        "vsrl.vi v16, v1, 27",
+        "vor.vv v10, v10, v16",
+        "vadd.vv v10, v10, v9",
+        "vadd.vv v10, v10, v5",
+        "vadd.vx v10, v10, {k}",
+        "vadd.vv v10, v10, v8",
+        "vmv.v.v v5, v4",
+        "vmv.v.v v4, v3",
+        "vsll.vi v16, v2, 30",
+        "vsrl.vi v3, v2, 2",
+        "vor.vv v3, v3, v16",
+        "vmv.v.v v2, v1",
+        "vmv.v.v v1, v10",
+        "add {p}, {p}, {stride}",
+        "addi {i}, {i}, -1",
+        "bnez {i}, 2b",
+
+        // rounds 21-40, f = b ^ c ^ d
+        "li {k}, 0x6ED9EBA1",
+        "li {i}, 20",
+        "3:",
+        "vle32.v v8, ({p})",
+        "vxor.vv v9, v2, v3",
+        "vxor.vv v9, v9, v4",
+        "vsll.vi v10, v1, 5",
+        "vsrl.vi v16, v1, 27",
+        "vor.vv v10, v10, v16",
+        "vadd.vv v10, v10, v9",
+        "vadd.vv v10, v10, v5",
+        "vadd.vx v10, v10, {k}",
+        "vadd.vv v10, v10, v8",
+        "vmv.v.v v5, v4",
+        "vmv.v.v v4, v3",
+        "vsll.vi v16, v2, 30",
+        "vsrl.vi v3, v2, 2",
+        "vor.vv v3, v3, v16",
+        "vmv.v.v v2, v1",
+        "vmv.v.v v1, v10",
+        "add {p}, {p}, {stride}",
+        "addi {i}, {i}, -1",
+        "bnez {i}, 3b",
+
+        // rounds 41-60, f = (b & c) | (d & (b | c))
+        "li {k}, 0x8F1BBCDC",
+        "li {i}, 20",
+        "4:",
+        "vle32.v v8, ({p})",
+        "vand.vv v9, v2, v3",
+        "vor.vv v16, v2, v3",
+        "vand.vv v16, v16, v4",
+        "vor.vv v9, v9, v16",
+        "vsll.vi v10, v1, 5",
+        "vsrl.vi v16, v1, 27",
+        "vor.vv v10, v10, v16",
+        "vadd.vv v10, v10, v9",
+        "vadd.vv v10, v10, v5",
+        "vadd.vx v10, v10, {k}",
+        "vadd.vv v10, v10, v8",
+        "vmv.v.v v5, v4",
+        "vmv.v.v v4, v3",
+        "vsll.vi v16, v2, 30",
+        "vsrl.vi v3, v2, 2",
+        "vor.vv v3, v3, v16",
+        "vmv.v.v v2, v1",
+        "vmv.v.v v1, v10",
+        "add {p}, {p}, {stride}",
+        "addi {i}, {i}, -1",
+        "bnez {i}, 4b",
+
+        // rounds 61-80, f = b ^ c ^ d
+        "li {k}, 0xCA62C1D6",
+        "li {i}, 20",
+        "5:",
+        "vle32.v v8, ({p})",
+        "vxor.vv v9, v2, v3",
+        "vxor.vv v9, v9, v4",
+        "vsll.vi v10, v1, 5",
+        "vsrl.vi v16, v1, 27",
+        "vor.vv v10, v10, v16",
+        "vadd.vv v10, v10, v9",
+        "vadd.vv v10, v10, v5",
+        "vadd.vx v10, v10, {k}",
+        "vadd.vv v10, v10, v8",
+        "vmv.v.v v5, v4",
+        "vmv.v.v v4, v3",
+        "vsll.vi v16, v2, 30",
+        "vsrl.vi v3, v2, 2",
+        "vor.vv v3, v3, v16",
+        "vmv.v.v v2, v1",
+        "vmv.v.v v1, v10",
+        "add {p}, {p}, {stride}",
+        "addi {i}, {i}, -1",
+        "bnez {i}, 5b",
+
+        // hash value += a..e in active lanes
+        "vle32.v v17, ({active})",
+        "vand.vv v1, v1, v17",
+        "vand.vv v2, v2, v17",
+        "vand.vv v3, v3, v17",
+        "vand.vv v4, v4, v17",
+        "vand.vv v5, v5, v17",
+        "vadd.vv v1, v1, v11",
+        "vadd.vv v2, v2, v12",
+        "vadd.vv v3, v3, v13",
+        "vadd.vv v4, v4, v14",
+        "vadd.vv v5, v5, v15",
+        "mv {q}, {state}",
+        "vse32.v v1, ({q})",
+        "add {q}, {q}, {stride}",
+        "vse32.v v2, ({q})",
+        "add {q}, {q}, {stride}",
+        "vse32.v v3, ({q})",
+        "add {q}, {q}, {stride}",
+        "vse32.v v4, ({q})",
+        "add {q}, {q}, {stride}",
+        "vse32.v v5, ({q})",
+        ".option pop",
+        lanes = in(reg) lanes,
+        state = in(reg) state.as_mut_ptr(),
+        w = in(reg) w.as_mut_ptr(),
+        active = in(reg) active.as_ptr(),
+        stride = in(reg) stride,
+        stride2 = in(reg) stride * 2,
+        stride8 = in(reg) stride * 8,
+        stride13 = in(reg) stride * 13,
+        stride16 = in(reg) stride * 16,
+        p = out(reg) _,
+        q = out(reg) _,
+        i = out(reg) _,
+        k = out(reg) _,
+        out("v1") _, out("v2") _, out("v3") _, out("v4") _, out("v5") _,
+        out("v8") _, out("v9") _, out("v10") _, out("v11") _, out("v12") _,
+        out("v13") _, out("v14") _, out("v15") _, out("v16") _, out("v17") _,
+        options(nostack),
+    );
+}
@@ -0,0 +1,174 @@
+const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// # Examples
+///
+/// ```
+/// let data = "The quick brown fox jumps over the lazy dog".as_bytes();
+/// let expect = [
+///     0x2F, 0xD4, 0xE1, 0xC6, 0x7A, 0x2D, 0x28, 0xFC, 0xED, 0x84,
+///     0x9E, 0xE1, 0xBB, 0x76, 0xE7, 0x39, 0x1B, 0x93, 0xEB, 0x12
+/// ];
+///
+/// let digest = simd_sha1::hash(&data);
+///
+/// assert_eq!(digest, expect);
+/// ```
+pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let message = padding(bytes);
+    let mut hash_value = HASH_VALUE;
+    for block in message.chunks(64) {
+        hash_value = hash_block(hash_value, block);
+    }
+
+    let [h0, h1, h2, h3, h4] = hash_value;
+
+    let mut digest = [0; 20];
+    digest[00..04].copy_from_slice(&h0.to_be_bytes());
+    digest[04..08].copy_from_slice(&h1.to_be_bytes());
+    digest[08..12].copy_from_slice(&h2.to_be_bytes());
+    digest[12..16].copy_from_slice(&h3.to_be_bytes());
+    digest[16..20].copy_from_slice(&h4.to_be_bytes());
+    return digest
+}
+
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        *state = hash_block(*state, block);
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        *state = hash_block_sha0(*state, block);
+    }
+}
+
+/// Hashes every message independently.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    return messages.iter().map(|bytes| hash(bytes)).collect()
+}
+
+#[inline]
+pub(crate) fn padding(bytes: &[u8]) -> Vec<u8> {
+    let original_length = bytes.len();
+    let zero_count = (119 - original_length % 64) % 64;
+    let mut message = bytes.to_owned();
+    message.push(0x80);
+    message.extend(vec![0; zero_count]);
+    message.extend(((original_length as u64) << 3).to_be_bytes());
+    return message
+}
+
+#[inline]
+fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, &schedule::<1>(bytes))
+}
+
+#[inline]
+fn hash_block_sha0(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, &schedule::<0>(bytes))
+}
+
+/// `w[t] = (w[t-3] ^ w[t-8] ^ w[t-14] ^ w[t-16]) <<< R`, where R is 1 for SHA1 and 0 for SHA-0.
+#[inline]
+pub(crate) fn schedule<const R: u32>(bytes: &[u8]) -> [u32; 80] {
+    let mut w = [0u32; 80];
+    for (word, bytes) in w.iter_mut().zip(bytes.chunks(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(R);
+    }
+    return w
+}
+
+#[inline]
+fn rounds(hash_value: [u32; 5], w: &[u32; 80]) -> [u32; 5] {
+    let abcde = hash_value;
+    // rounds 1-20
+    let abcde = compute(abcde, &w[00..20], 0x5A827999, choose);
+    // rounds 21-40
+    let abcde = compute(abcde, &w[20..40], 0x6ED9EBA1, parity);
+    // rounds 41-60
+    let abcde = compute(abcde, &w[40..60], 0x8F1BBCDC, majority);
+    // rounds 61-80
+    let abcde = compute(abcde, &w[60..80], 0xCA62C1D6, parity);
+
+    let [a, b, c, d, e] = abcde;
+    let [h0, h1, h2, h3, h4] = hash_value;
+    return [
+        a.wrapping_add(h0),
+        b.wrapping_add(h1),
+        c.wrapping_add(h2),
+        d.wrapping_add(h3),
+        e.wrapping_add(h4),
+    ]
+}
+
+#[inline]
+pub(crate) fn compute(abcde: [u32; 5], w: &[u32], k: u32, function: fn(u32, u32, u32) -> u32) -> [u32; 5] {
+    let [mut a, mut b, mut c, mut d, mut e] = abcde;
+
+    for wt in w {
+        let tmp = e.wrapping_add(a.rotate_left(5)).wrapping_add(function(b, c, d)).wrapping_add(wt.wrapping_add(k));
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = tmp;
+    }
+
+    return [a, b, c, d, e]
+}
+
+#[inline]
+pub(crate) fn choose(b: u32, c: u32, d: u32) -> u32 {
+    return b & c ^ !b & d
+}
+
+#[inline]
+pub(crate) fn majority(b: u32, c: u32, d: u32) -> u32 {
+    return b & c ^ b & d ^ c & d
+}
+
+#[inline]
+pub(crate) fn parity(b: u32, c: u32, d: u32) -> u32 {
+    return b ^ c ^ d
+}
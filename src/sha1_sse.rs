@@ -19,6 +19,8 @@ const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0x
 /// assert_eq!(digest, expect);
 /// ```
 pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
     let message = padding(bytes);
     let mut hash_value = HASH_VALUE;
     for block in message.0.chunks(64) {
@@ -38,13 +40,87 @@ pub fn hash(bytes: &[u8]) -> [u8; 20] {
     return digest
 }
 
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        unsafe {
+            *state = hash_block(*state, block);
+        }
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        unsafe {
+            *state = hash_block_sha0(*state, block);
+        }
+    }
+}
+
+/// Hashes every message independently, four at a time in the lanes of `__m128i`.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let mut digests = Vec::with_capacity(messages.len());
+    for group in messages.chunks(4) {
+        let mut lanes: [Vec<u8>; 4] = Default::default();
+        for (lane, bytes) in lanes.iter_mut().zip(group) {
+            *lane = padding(bytes).0;
+        }
+
+        let hash_values = unsafe { hash_lanes(&lanes) };
+        for [h0, h1, h2, h3, h4] in &hash_values[..group.len()] {
+            let mut digest = [0; 20];
+            digest[00..04].copy_from_slice(&h0.to_be_bytes());
+            digest[04..08].copy_from_slice(&h1.to_be_bytes());
+            digest[08..12].copy_from_slice(&h2.to_be_bytes());
+            digest[12..16].copy_from_slice(&h3.to_be_bytes());
+            digest[16..20].copy_from_slice(&h4.to_be_bytes());
+            digests.push(digest);
+        }
+    }
+    return digests
+}
+
 #[repr(align(16))]
 struct Align16<T>(T);
 
 #[inline]
 fn padding(bytes: &[u8]) -> Align16<Vec<u8>> {
     let original_length = bytes.len();
-    let zero_count = ((original_length + 64) & !63) - original_length - 9;
+    let zero_count = (119 - original_length % 64) % 64;
     let mut message = bytes.to_owned();
     message.push(0x80);
     message.extend(vec![0; zero_count]);
@@ -53,7 +129,18 @@ fn padding(bytes: &[u8]) -> Align16<Vec<u8>> {
 }
 
 #[inline]
-unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+pub(crate) unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, schedule(bytes))
+}
+
+#[inline]
+unsafe fn hash_block_sha0(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, sha0_schedule(bytes))
+}
+
+/// The 80 words of the message schedule, four per vector.
+#[inline]
+unsafe fn schedule(bytes: &[u8]) -> [__m128i; 20] {
     let [w00_w03, w04_w07, w08_w11, w12_w15] = schedule_v0(bytes);
     let w16_w19 = schedule_v1(w00_w03, w04_w07, w08_w11, w12_w15);
     let w20_w23 = schedule_v1(w04_w07, w08_w11, w12_w15, w16_w19);
@@ -71,6 +158,50 @@ unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
     let w68_w71 = schedule_v3(w04_w07, w12_w15, w36_w39, w56_w59);
     let w72_w75 = schedule_v3(w08_w11, w16_w19, w40_w43, w60_w63);
     let w76_w79 = schedule_v3(w12_w15, w20_w23, w44_w47, w64_w67);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+/// The SHA-0 message schedule, which has no rotation in the expansion.
+#[inline]
+unsafe fn sha0_schedule(bytes: &[u8]) -> [__m128i; 20] {
+    let [w00_w03, w04_w07, w08_w11, w12_w15] = schedule_v0(bytes);
+    let w16_w19 = sha0_schedule_v1(w00_w03, w04_w07, w08_w11, w12_w15);
+    let w20_w23 = sha0_schedule_v1(w04_w07, w08_w11, w12_w15, w16_w19);
+    let w24_w27 = sha0_schedule_v1(w08_w11, w12_w15, w16_w19, w20_w23);
+    let w28_w31 = sha0_schedule_v1(w12_w15, w16_w19, w20_w23, w24_w27);
+    let w32_w35 = sha0_schedule_v2(w00_w03, w04_w07, w16_w19, w24_w27, w28_w31);
+    let w36_w39 = sha0_schedule_v2(w04_w07, w08_w11, w20_w23, w28_w31, w32_w35);
+    let w40_w43 = sha0_schedule_v2(w08_w11, w12_w15, w24_w27, w32_w35, w36_w39);
+    let w44_w47 = sha0_schedule_v2(w12_w15, w16_w19, w28_w31, w36_w39, w40_w43);
+    let w48_w51 = sha0_schedule_v2(w16_w19, w20_w23, w32_w35, w40_w43, w44_w47);
+    let w52_w55 = sha0_schedule_v2(w20_w23, w24_w27, w36_w39, w44_w47, w48_w51);
+    let w56_w59 = sha0_schedule_v2(w24_w27, w28_w31, w40_w43, w48_w51, w52_w55);
+    let w60_w63 = sha0_schedule_v2(w28_w31, w32_w35, w44_w47, w52_w55, w56_w59);
+    let w64_w67 = sha0_schedule_v3(w00_w03, w08_w11, w32_w35, w52_w55);
+    let w68_w71 = sha0_schedule_v3(w04_w07, w12_w15, w36_w39, w56_w59);
+    let w72_w75 = sha0_schedule_v3(w08_w11, w16_w19, w40_w43, w60_w63);
+    let w76_w79 = sha0_schedule_v3(w12_w15, w20_w23, w44_w47, w64_w67);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+#[inline]
+unsafe fn rounds(hash_value: [u32; 5], w: [__m128i; 20]) -> [u32; 5] {
+    let [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ] = w;
 
     let abcde = hash_value;
     // rounds 1-20
@@ -126,10 +257,10 @@ unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
 #[cfg(target_feature = "ssse3")]
 unsafe fn schedule_v0(bytes: &[u8]) -> [__m128i; 4] {
     let mask = _mm_set_epi64x(0x0c0d0e0f08090a0b, 0x0405060700010203);
-    let q0 = _mm_load_si128(bytes.as_ptr().cast());
-    let q1 = _mm_load_si128(bytes.as_ptr().add(16).cast());
-    let q2 = _mm_load_si128(bytes.as_ptr().add(32).cast());
-    let q3 = _mm_load_si128(bytes.as_ptr().add(48).cast());
+    let q0 = _mm_loadu_si128(bytes.as_ptr().cast());
+    let q1 = _mm_loadu_si128(bytes.as_ptr().add(16).cast());
+    let q2 = _mm_loadu_si128(bytes.as_ptr().add(32).cast());
+    let q3 = _mm_loadu_si128(bytes.as_ptr().add(48).cast());
     return [
         _mm_shuffle_epi8(q0, mask), 
         _mm_shuffle_epi8(q1, mask),
@@ -220,6 +351,47 @@ unsafe fn schedule_v3(w0_3: __m128i, w8_11: __m128i, w32_35: __m128i, w52_55: __
     return w64_67
 }
 
+/// |     if 16 <= t <= 79     |
+/// |:------------------------:|
+/// |w16 = w13 ^ w8  ^ w2 ^ w0 |
+/// |w17 = w14 ^ w9  ^ w3 ^ w1 |
+/// |w18 = w15 ^ w10 ^ w4 ^ w2 |
+/// |w19 = w16 ^ w11 ^ w5 ^ w3 |
+#[inline]
+unsafe fn sha0_schedule_v1(w0_3: __m128i, w4_7: __m128i, w8_11: __m128i, w12_15: __m128i) -> __m128i {
+    let w13_15 = _mm_srli_si128::<4>(w12_15);
+    let w2_5 = half_and_half(w0_3, w4_7);
+    let w16_18 = _mm_xor_si128(_mm_xor_si128(w13_15, w8_11), _mm_xor_si128(w2_5, w0_3));
+    let w16 = _mm_slli_si128::<12>(w16_18);
+    let w16_19 = _mm_xor_si128(w16_18, w16);
+    return w16_19
+}
+
+/// |     if 32 <= t <= 79     |
+/// |:------------------------:|
+/// |w32 = w26 ^ w16 ^ w4 ^ w0 |
+/// |w33 = w27 ^ w17 ^ w5 ^ w1 |
+/// |w34 = w28 ^ w18 ^ w6 ^ w2 |
+/// |w35 = w29 ^ w19 ^ w7 ^ w3 |
+#[inline]
+unsafe fn sha0_schedule_v2(w0_3: __m128i, w4_7: __m128i, w16_19: __m128i, w24_27: __m128i, w28_31: __m128i) -> __m128i {
+    let w26_29 = half_and_half(w24_27, w28_31);
+    let w32_35 = _mm_xor_si128(_mm_xor_si128(w26_29, w16_19), _mm_xor_si128(w4_7, w0_3));
+    return w32_35
+}
+
+/// |     if 64 <= t <= 79      |
+/// |:-------------------------:|
+/// |w64 = w52 ^ w32 ^ w8  ^ w0 |
+/// |w65 = w53 ^ w33 ^ w9  ^ w1 |
+/// |w66 = w54 ^ w34 ^ w10 ^ w2 |
+/// |w67 = w55 ^ w35 ^ w11 ^ w3 |
+#[inline]
+unsafe fn sha0_schedule_v3(w0_3: __m128i, w8_11: __m128i, w32_35: __m128i, w52_55: __m128i) -> __m128i {
+    let w64_67 = _mm_xor_si128(_mm_xor_si128(w52_55, w32_35), _mm_xor_si128(w8_11, w0_3));
+    return w64_67
+}
+
 #[inline]
 #[cfg(target_feature = "ssse3")]
 unsafe fn half_and_half(a: __m128i, b: __m128i) -> __m128i {
@@ -264,4 +436,93 @@ fn majority(b: u32, c: u32, d: u32) -> u32 {
 #[inline]
 fn parity(b: u32, c: u32, d: u32) -> u32 {
     return b ^ c ^ d
+}
+
+/// Runs the blocks of four padded messages side by side, one message per lane.
+/// A lane whose message has no more blocks keeps its hash value.
+#[inline]
+unsafe fn hash_lanes(lanes: &[Vec<u8>; 4]) -> [[u32; 5]; 4] {
+    let block_count = lanes.iter().map(|lane| lane.len() / 64).max().unwrap_or(0);
+    let mut hash_value = HASH_VALUE.map(|h| _mm_set1_epi32(h as i32));
+    for index in 0..block_count {
+        let mut words = [[0u32; 16]; 4];
+        let mut active = [0i32; 4];
+        for lane in 0..4 {
+            if let Some(block) = lanes[lane].get(index * 64..index * 64 + 64) {
+                for (word, bytes) in words[lane].iter_mut().zip(block.chunks(4)) {
+                    *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                }
+                active[lane] = -1;
+            }
+        }
+
+        let w = std::array::from_fn(|t| {
+            _mm_set_epi32(words[3][t] as i32, words[2][t] as i32, words[1][t] as i32, words[0][t] as i32)
+        });
+        let mask = _mm_set_epi32(active[3], active[2], active[1], active[0]);
+        let next = hash_block_x4(hash_value, w);
+        for (h, n) in hash_value.iter_mut().zip(next) {
+            *h = _mm_or_si128(_mm_and_si128(mask, n), _mm_andnot_si128(mask, *h));
+        }
+    }
+
+    let mut buffer = [[0u32; 4]; 5];
+    for (buffer, h) in buffer.iter_mut().zip(hash_value) {
+        _mm_storeu_si128(buffer.as_mut_ptr().cast(), h);
+    }
+    return std::array::from_fn(|lane| std::array::from_fn(|i| buffer[i][lane]))
+}
+
+#[inline]
+unsafe fn hash_block_x4(hash_value: [__m128i; 5], mut w: [__m128i; 16]) -> [__m128i; 5] {
+    let mut abcde = hash_value;
+    for t in 0..80 {
+        if t >= 16 {
+            let sum = _mm_xor_si128(
+                _mm_xor_si128(w[(t - 3) & 15], w[(t - 8) & 15]),
+                _mm_xor_si128(w[(t - 14) & 15], w[t & 15])
+            );
+            w[t & 15] = rotate_left_x4::<1, 31>(sum);
+        }
+        abcde = match t / 20 {
+            0 => compute_x4(abcde, w[t & 15], 0x5A827999, choose_x4),
+            1 => compute_x4(abcde, w[t & 15], 0x6ED9EBA1, parity_x4),
+            2 => compute_x4(abcde, w[t & 15], 0x8F1BBCDC, majority_x4),
+            _ => compute_x4(abcde, w[t & 15], 0xCA62C1D6, parity_x4),
+        };
+    }
+
+    let mut hash_value = hash_value;
+    for (h, x) in hash_value.iter_mut().zip(abcde) {
+        *h = _mm_add_epi32(*h, x);
+    }
+    return hash_value
+}
+
+#[inline]
+unsafe fn compute_x4(abcde: [__m128i; 5], w: __m128i, k: u32, function: unsafe fn(__m128i, __m128i, __m128i) -> __m128i) -> [__m128i; 5] {
+    let [a, b, c, d, e] = abcde;
+    let wk = _mm_add_epi32(w, _mm_set1_epi32(k as i32));
+    let tmp = _mm_add_epi32(_mm_add_epi32(e, rotate_left_x4::<5, 27>(a)), _mm_add_epi32(function(b, c, d), wk));
+    return [tmp, a, rotate_left_x4::<30, 2>(b), c, d]
+}
+
+#[inline]
+unsafe fn rotate_left_x4<const L: i32, const R: i32>(x: __m128i) -> __m128i {
+    return _mm_or_si128(_mm_slli_epi32::<L>(x), _mm_srli_epi32::<R>(x))
+}
+
+#[inline]
+unsafe fn choose_x4(b: __m128i, c: __m128i, d: __m128i) -> __m128i {
+    return _mm_xor_si128(_mm_and_si128(b, c), _mm_andnot_si128(b, d))
+}
+
+#[inline]
+unsafe fn majority_x4(b: __m128i, c: __m128i, d: __m128i) -> __m128i {
+    return _mm_or_si128(_mm_and_si128(b, c), _mm_and_si128(d, _mm_or_si128(b, c)))
+}
+
+#[inline]
+unsafe fn parity_x4(b: __m128i, c: __m128i, d: __m128i) -> __m128i {
+    return _mm_xor_si128(_mm_xor_si128(b, c), d)
 }
\ No newline at end of file
@@ -0,0 +1,504 @@
+#[cfg(target_arch = "wasm32")]
+use std::arch::wasm32::*;
+
+const HASH_VALUE: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// # Examples
+///
+/// ```
+/// let data = "The quick brown fox jumps over the lazy dog".as_bytes();
+/// let expect = [
+///     0x2F, 0xD4, 0xE1, 0xC6, 0x7A, 0x2D, 0x28, 0xFC, 0xED, 0x84,
+///     0x9E, 0xE1, 0xBB, 0x76, 0xE7, 0x39, 0x1B, 0x93, 0xEB, 0x12
+/// ];
+///
+/// let digest = simd_sha1::hash(&data);
+///
+/// assert_eq!(digest, expect);
+/// ```
+pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let message = padding(bytes);
+    let mut hash_value = HASH_VALUE;
+    for block in message.chunks(64) {
+        unsafe {
+            hash_value = hash_block(hash_value, block);
+        }
+    }
+
+    let [h0, h1, h2, h3, h4] = hash_value;
+
+    let mut digest = [0; 20];
+    digest[00..04].copy_from_slice(&h0.to_be_bytes());
+    digest[04..08].copy_from_slice(&h1.to_be_bytes());
+    digest[08..12].copy_from_slice(&h2.to_be_bytes());
+    digest[12..16].copy_from_slice(&h3.to_be_bytes());
+    digest[16..20].copy_from_slice(&h4.to_be_bytes());
+    return digest
+}
+
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        unsafe {
+            *state = hash_block(*state, block);
+        }
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    for block in blocks {
+        unsafe {
+            *state = hash_block_sha0(*state, block);
+        }
+    }
+}
+
+/// Hashes every message independently, four at a time in the lanes of `v128`.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let mut digests = Vec::with_capacity(messages.len());
+    for group in messages.chunks(4) {
+        let mut lanes: [Vec<u8>; 4] = Default::default();
+        for (lane, bytes) in lanes.iter_mut().zip(group) {
+            *lane = padding(bytes);
+        }
+
+        let hash_values = unsafe { hash_lanes(&lanes) };
+        for [h0, h1, h2, h3, h4] in &hash_values[..group.len()] {
+            let mut digest = [0; 20];
+            digest[00..04].copy_from_slice(&h0.to_be_bytes());
+            digest[04..08].copy_from_slice(&h1.to_be_bytes());
+            digest[08..12].copy_from_slice(&h2.to_be_bytes());
+            digest[12..16].copy_from_slice(&h3.to_be_bytes());
+            digest[16..20].copy_from_slice(&h4.to_be_bytes());
+            digests.push(digest);
+        }
+    }
+    return digests
+}
+
+#[inline]
+fn padding(bytes: &[u8]) -> Vec<u8> {
+    let original_length = bytes.len();
+    let zero_count = (119 - original_length % 64) % 64;
+    let mut message = bytes.to_owned();
+    message.push(0x80);
+    message.extend(vec![0; zero_count]);
+    message.extend(((original_length as u64) << 3).to_be_bytes());
+    return message
+}
+
+#[inline]
+unsafe fn hash_block(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, schedule(bytes))
+}
+
+#[inline]
+unsafe fn hash_block_sha0(hash_value: [u32; 5], bytes: &[u8]) -> [u32; 5] {
+    return rounds(hash_value, sha0_schedule(bytes))
+}
+
+/// The 80 words of the message schedule, four per vector.
+#[inline]
+unsafe fn schedule(bytes: &[u8]) -> [v128; 20] {
+    let [w00_w03, w04_w07, w08_w11, w12_w15] = schedule_v0(bytes);
+    let w16_w19 = schedule_v1(w00_w03, w04_w07, w08_w11, w12_w15);
+    let w20_w23 = schedule_v1(w04_w07, w08_w11, w12_w15, w16_w19);
+    let w24_w27 = schedule_v1(w08_w11, w12_w15, w16_w19, w20_w23);
+    let w28_w31 = schedule_v1(w12_w15, w16_w19, w20_w23, w24_w27);
+    let w32_w35 = schedule_v2(w00_w03, w04_w07, w16_w19, w24_w27, w28_w31);
+    let w36_w39 = schedule_v2(w04_w07, w08_w11, w20_w23, w28_w31, w32_w35);
+    let w40_w43 = schedule_v2(w08_w11, w12_w15, w24_w27, w32_w35, w36_w39);
+    let w44_w47 = schedule_v2(w12_w15, w16_w19, w28_w31, w36_w39, w40_w43);
+    let w48_w51 = schedule_v2(w16_w19, w20_w23, w32_w35, w40_w43, w44_w47);
+    let w52_w55 = schedule_v2(w20_w23, w24_w27, w36_w39, w44_w47, w48_w51);
+    let w56_w59 = schedule_v2(w24_w27, w28_w31, w40_w43, w48_w51, w52_w55);
+    let w60_w63 = schedule_v2(w28_w31, w32_w35, w44_w47, w52_w55, w56_w59);
+    let w64_w67 = schedule_v3(w00_w03, w08_w11, w32_w35, w52_w55);
+    let w68_w71 = schedule_v3(w04_w07, w12_w15, w36_w39, w56_w59);
+    let w72_w75 = schedule_v3(w08_w11, w16_w19, w40_w43, w60_w63);
+    let w76_w79 = schedule_v3(w12_w15, w20_w23, w44_w47, w64_w67);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+/// The SHA-0 message schedule, which has no rotation in the expansion.
+#[inline]
+unsafe fn sha0_schedule(bytes: &[u8]) -> [v128; 20] {
+    let [w00_w03, w04_w07, w08_w11, w12_w15] = schedule_v0(bytes);
+    let w16_w19 = sha0_schedule_v1(w00_w03, w04_w07, w08_w11, w12_w15);
+    let w20_w23 = sha0_schedule_v1(w04_w07, w08_w11, w12_w15, w16_w19);
+    let w24_w27 = sha0_schedule_v1(w08_w11, w12_w15, w16_w19, w20_w23);
+    let w28_w31 = sha0_schedule_v1(w12_w15, w16_w19, w20_w23, w24_w27);
+    let w32_w35 = sha0_schedule_v2(w00_w03, w04_w07, w16_w19, w24_w27, w28_w31);
+    let w36_w39 = sha0_schedule_v2(w04_w07, w08_w11, w20_w23, w28_w31, w32_w35);
+    let w40_w43 = sha0_schedule_v2(w08_w11, w12_w15, w24_w27, w32_w35, w36_w39);
+    let w44_w47 = sha0_schedule_v2(w12_w15, w16_w19, w28_w31, w36_w39, w40_w43);
+    let w48_w51 = sha0_schedule_v2(w16_w19, w20_w23, w32_w35, w40_w43, w44_w47);
+    let w52_w55 = sha0_schedule_v2(w20_w23, w24_w27, w36_w39, w44_w47, w48_w51);
+    let w56_w59 = sha0_schedule_v2(w24_w27, w28_w31, w40_w43, w48_w51, w52_w55);
+    let w60_w63 = sha0_schedule_v2(w28_w31, w32_w35, w44_w47, w52_w55, w56_w59);
+    let w64_w67 = sha0_schedule_v3(w00_w03, w08_w11, w32_w35, w52_w55);
+    let w68_w71 = sha0_schedule_v3(w04_w07, w12_w15, w36_w39, w56_w59);
+    let w72_w75 = sha0_schedule_v3(w08_w11, w16_w19, w40_w43, w60_w63);
+    let w76_w79 = sha0_schedule_v3(w12_w15, w20_w23, w44_w47, w64_w67);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+#[inline]
+unsafe fn rounds(hash_value: [u32; 5], w: [v128; 20]) -> [u32; 5] {
+    let [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ] = w;
+
+    let abcde = hash_value;
+    // rounds 1-20
+    let kx4 = u32x4_splat(0x5A827999);
+    let abcde = compute(abcde, w00_w03, kx4, choose);
+    let abcde = compute(abcde, w04_w07, kx4, choose);
+    let abcde = compute(abcde, w08_w11, kx4, choose);
+    let abcde = compute(abcde, w12_w15, kx4, choose);
+    let abcde = compute(abcde, w16_w19, kx4, choose);
+
+    // rounds 21-40
+    let kx4 = u32x4_splat(0x6ED9EBA1);
+    let abcde = compute(abcde, w20_w23, kx4, parity);
+    let abcde = compute(abcde, w24_w27, kx4, parity);
+    let abcde = compute(abcde, w28_w31, kx4, parity);
+    let abcde = compute(abcde, w32_w35, kx4, parity);
+    let abcde = compute(abcde, w36_w39, kx4, parity);
+
+    // rounds 41-60
+    let kx4 = u32x4_splat(0x8F1BBCDC);
+    let abcde = compute(abcde, w40_w43, kx4, majority);
+    let abcde = compute(abcde, w44_w47, kx4, majority);
+    let abcde = compute(abcde, w48_w51, kx4, majority);
+    let abcde = compute(abcde, w52_w55, kx4, majority);
+    let abcde = compute(abcde, w56_w59, kx4, majority);
+
+    // rounds 61-80
+    let kx4 = u32x4_splat(0xCA62C1D6);
+    let abcde = compute(abcde, w60_w63, kx4, parity);
+    let abcde = compute(abcde, w64_w67, kx4, parity);
+    let abcde = compute(abcde, w68_w71, kx4, parity);
+    let abcde = compute(abcde, w72_w75, kx4, parity);
+    let abcde = compute(abcde, w76_w79, kx4, parity);
+
+    let [a, b, c, d, e] = abcde;
+    let [h0, h1, h2, h3, h4] = hash_value;
+    return [
+        a.wrapping_add(h0),
+        b.wrapping_add(h1),
+        c.wrapping_add(h2),
+        d.wrapping_add(h3),
+        e.wrapping_add(h4),
+    ]
+}
+
+/// |if 0 <= t <= 15|
+/// |:-------------:|
+/// |    w0 = m0    |
+/// |    w1 = m1    |
+/// |    w2 = m2    |
+/// |    w3 = m3    |
+#[inline]
+unsafe fn schedule_v0(bytes: &[u8]) -> [v128; 4] {
+    let q0 = v128_load(bytes.as_ptr().cast());
+    let q1 = v128_load(bytes.as_ptr().add(16).cast());
+    let q2 = v128_load(bytes.as_ptr().add(32).cast());
+    let q3 = v128_load(bytes.as_ptr().add(48).cast());
+    return [
+        byte_swap(q0),
+        byte_swap(q1),
+        byte_swap(q2),
+        byte_swap(q3)
+    ]
+}
+
+/// |       if 16 <= t <= 79          |
+/// |:-------------------------------:|
+/// |w16 = (w13 ^ w8  ^ w2 ^ w0) <<< 1|
+/// |w17 = (w14 ^ w9  ^ w3 ^ w1) <<< 1|
+/// |w18 = (w15 ^ w10 ^ w4 ^ w2) <<< 1|
+/// |w19 = (w16 ^ w11 ^ w5 ^ w3) <<< 1|
+#[inline]
+unsafe fn schedule_v1(w0_3: v128, w4_7: v128, w8_11: v128, w12_15: v128) -> v128 {
+    let zero = u32x4_splat(0);
+    let w13_15 = i8x16_shuffle::<4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19>(w12_15, zero);
+    let w2_5 = half_and_half(w0_3, w4_7);
+    let sum = v128_xor(v128_xor(w13_15, w8_11), v128_xor(w2_5, w0_3));
+    let w16_18 = v128_xor(u32x4_shr(sum, 31), u32x4_shl(sum, 1));
+    let w16 = i8x16_shuffle::<0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 16, 17, 18, 19>(zero, w16_18);
+    let w16rol1 = v128_xor(u32x4_shr(w16, 31), u32x4_shl(w16, 1));
+    let w16_19 = v128_xor(w16_18, w16rol1);
+    return w16_19
+}
+
+/// |         if 32 <= t <= 79        |
+/// |:-------------------------------:|
+/// |w32 = (w26 ^ w16 ^ w4 ^ w0) <<< 2|
+/// |w33 = (w27 ^ w17 ^ w5 ^ w1) <<< 2|
+/// |w34 = (w28 ^ w18 ^ w6 ^ w2) <<< 2|
+/// |w35 = (w29 ^ w19 ^ w7 ^ w3) <<< 2|
+#[inline]
+unsafe fn schedule_v2(w0_3: v128, w4_7: v128, w16_19: v128, w24_27: v128, w28_31: v128) -> v128 {
+    let w26_29 = half_and_half(w24_27, w28_31);
+    let sum = v128_xor(v128_xor(w26_29, w16_19), v128_xor(w4_7, w0_3));
+    let w32_35 = v128_xor(u32x4_shr(sum, 30), u32x4_shl(sum, 2));
+    return w32_35
+}
+
+/// |         if 64 <= t <= 79         |
+/// |:--------------------------------:|
+/// |w64 = (w52 ^ w32 ^ w8  ^ w0) <<< 4|
+/// |w65 = (w53 ^ w33 ^ w9  ^ w1) <<< 4|
+/// |w66 = (w54 ^ w34 ^ w10 ^ w2) <<< 4|
+/// |w67 = (w55 ^ w35 ^ w11 ^ w3) <<< 4|
+#[inline]
+unsafe fn schedule_v3(w0_3: v128, w8_11: v128, w32_35: v128, w52_55: v128) -> v128 {
+    let sum = v128_xor(v128_xor(w52_55, w32_35), v128_xor(w8_11, w0_3));
+    let w64_67 = v128_xor(u32x4_shr(sum, 28), u32x4_shl(sum, 4));
+    return w64_67
+}
+
+/// |     if 16 <= t <= 79     |
+/// |:------------------------:|
+/// |w16 = w13 ^ w8  ^ w2 ^ w0 |
+/// |w17 = w14 ^ w9  ^ w3 ^ w1 |
+/// |w18 = w15 ^ w10 ^ w4 ^ w2 |
+/// |w19 = w16 ^ w11 ^ w5 ^ w3 |
+#[inline]
+unsafe fn sha0_schedule_v1(w0_3: v128, w4_7: v128, w8_11: v128, w12_15: v128) -> v128 {
+    let zero = u32x4_splat(0);
+    let w13_15 = i8x16_shuffle::<4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19>(w12_15, zero);
+    let w2_5 = half_and_half(w0_3, w4_7);
+    let w16_18 = v128_xor(v128_xor(w13_15, w8_11), v128_xor(w2_5, w0_3));
+    let w16 = i8x16_shuffle::<0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 16, 17, 18, 19>(zero, w16_18);
+    let w16_19 = v128_xor(w16_18, w16);
+    return w16_19
+}
+
+/// |     if 32 <= t <= 79     |
+/// |:------------------------:|
+/// |w32 = w26 ^ w16 ^ w4 ^ w0 |
+/// |w33 = w27 ^ w17 ^ w5 ^ w1 |
+/// |w34 = w28 ^ w18 ^ w6 ^ w2 |
+/// |w35 = w29 ^ w19 ^ w7 ^ w3 |
+#[inline]
+unsafe fn sha0_schedule_v2(w0_3: v128, w4_7: v128, w16_19: v128, w24_27: v128, w28_31: v128) -> v128 {
+    let w26_29 = half_and_half(w24_27, w28_31);
+    let w32_35 = v128_xor(v128_xor(w26_29, w16_19), v128_xor(w4_7, w0_3));
+    return w32_35
+}
+
+/// |     if 64 <= t <= 79      |
+/// |:-------------------------:|
+/// |w64 = w52 ^ w32 ^ w8  ^ w0 |
+/// |w65 = w53 ^ w33 ^ w9  ^ w1 |
+/// |w66 = w54 ^ w34 ^ w10 ^ w2 |
+/// |w67 = w55 ^ w35 ^ w11 ^ w3 |
+#[inline]
+unsafe fn sha0_schedule_v3(w0_3: v128, w8_11: v128, w32_35: v128, w52_55: v128) -> v128 {
+    let w64_67 = v128_xor(v128_xor(w52_55, w32_35), v128_xor(w8_11, w0_3));
+    return w64_67
+}
+
+#[inline]
+unsafe fn half_and_half(a: v128, b: v128) -> v128 {
+    return i8x16_shuffle::<8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23>(a, b)
+}
+
+#[inline]
+unsafe fn byte_swap(q: v128) -> v128 {
+    return i8x16_shuffle::<3, 2, 1, 0, 7, 6, 5, 4, 11, 10, 9, 8, 15, 14, 13, 12>(q, q)
+}
+
+#[inline]
+unsafe fn compute(abcde: [u32; 5], wx4: v128, kx4: v128, function: fn(u32, u32, u32) -> u32) -> [u32; 5] {
+    let mut wkx4 = [0u32; 4];
+    v128_store(wkx4.as_mut_ptr().cast(), u32x4_add(wx4, kx4));
+
+    let [mut a, mut b, mut c, mut d, mut e] = abcde;
+
+    for wk in wkx4 {
+        let tmp = e.wrapping_add(a.rotate_left(5)).wrapping_add(function(b, c, d)).wrapping_add(wk);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = tmp;
+    }
+
+    return [a, b, c, d, e]
+}
+
+#[inline]
+fn choose(b: u32, c: u32, d: u32) -> u32 {
+    return b & c ^ !b & d
+}
+
+#[inline]
+fn majority(b: u32, c: u32, d: u32) -> u32 {
+    return b & c ^ b & d ^ c & d
+}
+
+#[inline]
+fn parity(b: u32, c: u32, d: u32) -> u32 {
+    return b ^ c ^ d
+}
+
+/// Runs the blocks of four padded messages side by side, one message per lane.
+/// A lane whose message has no more blocks keeps its hash value.
+#[inline]
+unsafe fn hash_lanes(lanes: &[Vec<u8>; 4]) -> [[u32; 5]; 4] {
+    let block_count = lanes.iter().map(|lane| lane.len() / 64).max().unwrap_or(0);
+    let empty = [0u8; 64];
+    let mut hash_value = HASH_VALUE.map(|h| u32x4_splat(h));
+    for index in 0..block_count {
+        let mut active = [0u32; 4];
+        let blocks: [&[u8]; 4] = std::array::from_fn(|lane| match lanes[lane].get(index * 64..index * 64 + 64) {
+            Some(block) => {
+                active[lane] = u32::MAX;
+                block
+            }
+            None => &empty,
+        });
+
+        // four words of every lane per load, transposed into one word of all four lanes per vector
+        let mut w = [u32x4_splat(0); 16];
+        for (quarter, w) in w.chunks_mut(4).enumerate() {
+            let rows = blocks.map(|block| byte_swap(v128_load(block[quarter * 16..].as_ptr().cast())));
+            w.copy_from_slice(&transpose_x4(rows));
+        }
+        let [m0, m1, m2, m3] = active;
+        let mask = u32x4(m0, m1, m2, m3);
+        let next = hash_block_x4(hash_value, w);
+        for (h, n) in hash_value.iter_mut().zip(next) {
+            *h = v128_bitselect(n, *h, mask);
+        }
+    }
+
+    let mut buffer = [[0u32; 4]; 5];
+    for (buffer, h) in buffer.iter_mut().zip(hash_value) {
+        v128_store(buffer.as_mut_ptr().cast(), h);
+    }
+    return std::array::from_fn(|lane| std::array::from_fn(|i| buffer[i][lane]))
+}
+
+/// Transposes a 4x4 matrix of words: lane `j` of vector `i` of the result is lane `i` of `rows[j]`.
+#[inline]
+unsafe fn transpose_x4(rows: [v128; 4]) -> [v128; 4] {
+    let [r0, r1, r2, r3] = rows;
+    // [r0[0] r1[0] r0[1] r1[1]] and [r0[2] r1[2] r0[3] r1[3]]
+    let low01 = i32x4_shuffle::<0, 4, 1, 5>(r0, r1);
+    let high01 = i32x4_shuffle::<2, 6, 3, 7>(r0, r1);
+    let low23 = i32x4_shuffle::<0, 4, 1, 5>(r2, r3);
+    let high23 = i32x4_shuffle::<2, 6, 3, 7>(r2, r3);
+    return [
+        i32x4_shuffle::<0, 1, 4, 5>(low01, low23),
+        i32x4_shuffle::<2, 3, 6, 7>(low01, low23),
+        i32x4_shuffle::<0, 1, 4, 5>(high01, high23),
+        i32x4_shuffle::<2, 3, 6, 7>(high01, high23),
+    ]
+}
+
+#[inline]
+unsafe fn hash_block_x4(hash_value: [v128; 5], mut w: [v128; 16]) -> [v128; 5] {
+    let mut abcde = hash_value;
+    for t in 0..80 {
+        if t >= 16 {
+            let sum = v128_xor(
+                v128_xor(w[(t - 3) & 15], w[(t - 8) & 15]),
+                v128_xor(w[(t - 14) & 15], w[t & 15])
+            );
+            w[t & 15] = rotate_left_x4(sum, 1);
+        }
+        abcde = match t / 20 {
+            0 => compute_x4(abcde, w[t & 15], 0x5A827999, choose_x4),
+            1 => compute_x4(abcde, w[t & 15], 0x6ED9EBA1, parity_x4),
+            2 => compute_x4(abcde, w[t & 15], 0x8F1BBCDC, majority_x4),
+            _ => compute_x4(abcde, w[t & 15], 0xCA62C1D6, parity_x4),
+        };
+    }
+
+    let mut hash_value = hash_value;
+    for (h, x) in hash_value.iter_mut().zip(abcde) {
+        *h = u32x4_add(*h, x);
+    }
+    return hash_value
+}
+
+#[inline]
+unsafe fn compute_x4(abcde: [v128; 5], w: v128, k: u32, function: unsafe fn(v128, v128, v128) -> v128) -> [v128; 5] {
+    let [a, b, c, d, e] = abcde;
+    let wk = u32x4_add(w, u32x4_splat(k));
+    let tmp = u32x4_add(u32x4_add(e, rotate_left_x4(a, 5)), u32x4_add(function(b, c, d), wk));
+    return [tmp, a, rotate_left_x4(b, 30), c, d]
+}
+
+#[inline]
+unsafe fn rotate_left_x4(x: v128, n: u32) -> v128 {
+    return v128_or(u32x4_shl(x, n), u32x4_shr(x, 32 - n))
+}
+
+#[inline]
+unsafe fn choose_x4(b: v128, c: v128, d: v128) -> v128 {
+    return v128_bitselect(c, d, b)
+}
+
+#[inline]
+unsafe fn majority_x4(b: v128, c: v128, d: v128) -> v128 {
+    return v128_bitselect(d, b, v128_xor(b, c))
+}
+
+#[inline]
+unsafe fn parity_x4(b: v128, c: v128, d: v128) -> v128 {
+    return v128_xor(v128_xor(b, c), d)
+}
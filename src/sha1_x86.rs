@@ -23,6 +23,8 @@ const H4: i32 = 0xC3D2E1F0u32 as i32;
 /// assert_eq!(digest, expect);
 /// ```
 pub fn hash(bytes: &[u8]) -> [u8; 20] {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
     let message = padding(bytes);
     unsafe {
         let mut hash_value = [_mm_set_epi32(H0, H1, H2, H3), _mm_set_epi32(H4, 0, 0, 0)];
@@ -33,13 +35,145 @@ pub fn hash(bytes: &[u8]) -> [u8; 20] {
     }
 }
 
+/// Runs the compression function over `blocks`, starting from the chaining value in `state`.
+/// No padding is added.
+///
+/// # Examples
+///
+/// ```
+/// let mut block = [0; 64];
+/// block[..3].copy_from_slice("abc".as_bytes());
+/// block[3] = 0x80;
+/// block[63] = 24;
+///
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[block]);
+///
+/// assert_eq!(state, [0xA9993E36, 0x4706816A, 0xBA3E2571, 0x7850C26C, 0x9CD0D89D]);
+/// ```
+pub fn compress(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let [h0, h1, h2, h3, h4] = state.map(|h| h as i32);
+    unsafe {
+        let mut hash_value = [_mm_set_epi32(h0, h1, h2, h3), _mm_set_epi32(h4, 0, 0, 0)];
+        for block in blocks {
+            hash_value = hash_block(hash_value, block);
+        }
+
+        let mut buffer = [0u32; 4];
+        let [h0_3, h4] = hash_value;
+        _mm_storeu_si128(buffer.as_mut_ptr().cast(), h0_3);
+        let [h3, h2, h1, h0] = buffer;
+        _mm_storeu_si128(buffer.as_mut_ptr().cast(), h4);
+        *state = [h0, h1, h2, h3, buffer[3]];
+    }
+}
+
+/// SHA-0 counterpart of [`compress`].
+pub fn compress_sha0(state: &mut [u32; 5], blocks: &[[u8; 64]]) {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let [h0, h1, h2, h3, h4] = state.map(|h| h as i32);
+    unsafe {
+        let mut hash_value = [_mm_set_epi32(h0, h1, h2, h3), _mm_set_epi32(h4, 0, 0, 0)];
+        for block in blocks {
+            hash_value = hash_block_sha0(hash_value, block);
+        }
+
+        let mut buffer = [0u32; 4];
+        let [h0_3, h4] = hash_value;
+        _mm_storeu_si128(buffer.as_mut_ptr().cast(), h0_3);
+        let [h3, h2, h1, h0] = buffer;
+        _mm_storeu_si128(buffer.as_mut_ptr().cast(), h4);
+        *state = [h0, h1, h2, h3, buffer[3]];
+    }
+}
+
+/// Hashes every message independently.
+///
+/// Up to four messages are in flight at a time. Their blocks are interleaved instruction by
+/// instruction, so the SHA units are kept busy instead of waiting on the dependent chain of
+/// `_mm_sha1rnds4_epu32` of a single message.
+///
+/// # Examples
+///
+/// ```
+/// let messages = ["abc".as_bytes(), "".as_bytes(), "The quick brown fox jumps over the lazy dog".as_bytes()];
+///
+/// let digests = simd_sha1::hash_many(&messages);
+///
+/// for (message, digest) in messages.iter().zip(digests) {
+///     assert_eq!(digest, simd_sha1::hash(message));
+/// }
+/// ```
+pub fn hash_many(messages: &[&[u8]]) -> Vec<[u8; 20]> {
+    #[cfg(feature = "self_test")]
+    crate::self_test::ensure();
+    let mut digests = vec![[0; 20]; messages.len()];
+    let mut pending = messages.iter().enumerate();
+    let mut streams = Vec::with_capacity(STREAMS);
+    unsafe {
+        loop {
+            while streams.len() < STREAMS {
+                let Some((index, bytes)) = pending.next() else {
+                    break
+                };
+                let hash_value = [_mm_set_epi32(H0, H1, H2, H3), _mm_set_epi32(H4, 0, 0, 0)];
+                streams.push(Stream { index, message: padding(bytes), offset: 0, hash_value });
+            }
+            match streams.len() {
+                0 => break,
+                1 => step::<1>(&mut streams),
+                2 => step::<2>(&mut streams),
+                3 => step::<3>(&mut streams),
+                _ => step::<4>(&mut streams),
+            }
+            streams.retain(|stream| {
+                if stream.offset < stream.message.0.len() {
+                    return true
+                }
+                digests[stream.index] = finalize(stream.hash_value);
+                return false
+            });
+        }
+    }
+    return digests
+}
+
+/// Number of messages whose blocks are interleaved by `hash_many`.
+const STREAMS: usize = 4;
+
+struct Stream {
+    index: usize,
+    message: Align16<Vec<u8>>,
+    offset: usize,
+    hash_value: [__m128i; 2],
+}
+
+/// Hashes the first `N` streams until one of them runs out of blocks.
+#[inline]
+unsafe fn step<const N: usize>(streams: &mut [Stream]) {
+    let count = streams[..N].iter().map(|stream| stream.message.0.len() - stream.offset).min().unwrap_or(0);
+    let messages: [&[u8]; N] = std::array::from_fn(|i| &streams[i].message.0[streams[i].offset..][..count]);
+    let mut hash_value = std::array::from_fn(|i| streams[i].hash_value);
+    for offset in (0..count).step_by(64) {
+        let blocks = messages.map(|message| &message[offset..offset + 64]);
+        hash_value = hash_blocks::<N>(hash_value, blocks);
+    }
+    for (stream, hash_value) in streams.iter_mut().zip(hash_value) {
+        stream.hash_value = hash_value;
+        stream.offset += count;
+    }
+}
+
 #[repr(align(16))]
 struct Align16<T>(T);
 
 #[inline]
 fn padding(bytes: &[u8]) -> Align16<Vec<u8>> {
     let original_length = bytes.len();
-    let zero_count = ((original_length + 64) & !63) - original_length - 9;
+    let zero_count = (119 - original_length % 64) % 64;
     let mut message = bytes.to_owned();
     message.push(0x80);
     message.extend(vec![0; zero_count]);
@@ -49,6 +183,17 @@ fn padding(bytes: &[u8]) -> Align16<Vec<u8>> {
 
 #[inline]
 unsafe fn hash_block(hash_value: [__m128i; 2], bytes: &[u8]) -> [__m128i; 2] {
+    return rounds(hash_value, schedule(bytes))
+}
+
+#[inline]
+unsafe fn hash_block_sha0(hash_value: [__m128i; 2], bytes: &[u8]) -> [__m128i; 2] {
+    return rounds(hash_value, sha0_schedule(bytes))
+}
+
+/// The 80 words of the message schedule, four per vector.
+#[inline]
+unsafe fn schedule(bytes: &[u8]) -> [__m128i; 20] {
     let [w00_w03, w04_w07, w08_w11, w12_w15] = schedule_v0(bytes);
     let w16_w19 = _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w00_w03, w04_w07), w08_w11), w12_w15);
     let w20_w23 = _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w04_w07, w08_w11), w12_w15), w16_w19);
@@ -66,6 +211,51 @@ unsafe fn hash_block(hash_value: [__m128i; 2], bytes: &[u8]) -> [__m128i; 2] {
     let w68_w71 = _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w52_w55, w56_w59), w60_w63), w64_w67);
     let w72_w75 = _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w56_w59, w60_w63), w64_w67), w68_w71);
     let w76_w79 = _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w60_w63, w64_w67), w68_w71), w72_w75);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+/// The SHA-0 message schedule. `_mm_sha1msg1_epu32` only XORs and still applies, the rest of the
+/// step is done by `sha0_msg2` since `_mm_sha1msg2_epu32` rotates.
+#[inline]
+unsafe fn sha0_schedule(bytes: &[u8]) -> [__m128i; 20] {
+    let [w00_w03, w04_w07, w08_w11, w12_w15] = schedule_v0(bytes);
+    let w16_w19 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w00_w03, w04_w07), w08_w11), w12_w15);
+    let w20_w23 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w04_w07, w08_w11), w12_w15), w16_w19);
+    let w24_w27 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w08_w11, w12_w15), w16_w19), w20_w23);
+    let w28_w31 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w12_w15, w16_w19), w20_w23), w24_w27);
+    let w32_w35 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w16_w19, w20_w23), w24_w27), w28_w31);
+    let w36_w39 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w20_w23, w24_w27), w28_w31), w32_w35);
+    let w40_w43 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w24_w27, w28_w31), w32_w35), w36_w39);
+    let w44_w47 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w28_w31, w32_w35), w36_w39), w40_w43);
+    let w48_w51 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w32_w35, w36_w39), w40_w43), w44_w47);
+    let w52_w55 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w36_w39, w40_w43), w44_w47), w48_w51);
+    let w56_w59 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w40_w43, w44_w47), w48_w51), w52_w55);
+    let w60_w63 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w44_w47, w48_w51), w52_w55), w56_w59);
+    let w64_w67 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w48_w51, w52_w55), w56_w59), w60_w63);
+    let w68_w71 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w52_w55, w56_w59), w60_w63), w64_w67);
+    let w72_w75 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w56_w59, w60_w63), w64_w67), w68_w71);
+    let w76_w79 = sha0_msg2(_mm_xor_si128(_mm_sha1msg1_epu32(w60_w63, w64_w67), w68_w71), w72_w75);
+    return [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ]
+}
+
+#[inline]
+unsafe fn rounds(hash_value: [__m128i; 2], w: [__m128i; 20]) -> [__m128i; 2] {
+    let [
+        w00_w03, w04_w07, w08_w11, w12_w15, w16_w19,
+        w20_w23, w24_w27, w28_w31, w32_w35, w36_w39,
+        w40_w43, w44_w47, w48_w51, w52_w55, w56_w59,
+        w60_w63, w64_w67, w68_w71, w72_w75, w76_w79,
+    ] = w;
 
     let [abcd, e] = hash_value;
     let abcdew = [abcd, _mm_add_epi32(e, w00_w03)];
@@ -102,6 +292,90 @@ unsafe fn hash_block(hash_value: [__m128i; 2], bytes: &[u8]) -> [__m128i; 2] {
     return [_mm_add_epi32(abcd, h0_3), e]
 }
 
+/// `_mm_sha1msg2_epu32` without the rotation, words are in reversed order (w16 in the top lane).
+///
+/// |     if 16 <= t <= 79     |
+/// |:------------------------:|
+/// |w16 = w13 ^ w8  ^ w2 ^ w0 |
+/// |w17 = w14 ^ w9  ^ w3 ^ w1 |
+/// |w18 = w15 ^ w10 ^ w4 ^ w2 |
+/// |w19 = w16 ^ w11 ^ w5 ^ w3 |
+#[inline]
+unsafe fn sha0_msg2(sum: __m128i, w12_15: __m128i) -> __m128i {
+    let w16_18 = _mm_xor_si128(sum, _mm_slli_si128::<4>(w12_15));
+    let w16 = _mm_srli_si128::<12>(w16_18);
+    let w16_19 = _mm_xor_si128(w16_18, w16);
+    return w16_19
+}
+
+/// `hash_block` of `N` independent streams at once. Every step is issued for all streams before the
+/// next step, the message schedule is computed right before the rounds that need it.
+#[inline(always)]
+unsafe fn hash_blocks<const N: usize>(hash_value: [[__m128i; 2]; N], blocks: [&[u8]; N]) -> [[__m128i; 2]; N] {
+    let w = blocks.map(|bytes| schedule_v0(bytes));
+    let w00_w03 = w.map(|w| w[0]);
+    let w04_w07 = w.map(|w| w[1]);
+    let w08_w11 = w.map(|w| w[2]);
+    let w12_w15 = w.map(|w| w[3]);
+
+    let abcdew = std::array::from_fn(|i| [hash_value[i][0], _mm_add_epi32(hash_value[i][1], w00_w03[i])]);
+    // 1-20
+    let abcdew = compute_n::<0, N>(abcdew, w04_w07);
+    let abcdew = compute_n::<0, N>(abcdew, w08_w11);
+    let abcdew = compute_n::<0, N>(abcdew, w12_w15);
+    let w16_w19 = schedule_n(w00_w03, w04_w07, w08_w11, w12_w15);
+    let abcdew = compute_n::<0, N>(abcdew, w16_w19);
+    let w20_w23 = schedule_n(w04_w07, w08_w11, w12_w15, w16_w19);
+    let abcdew = compute_n::<0, N>(abcdew, w20_w23);
+
+    // 21-40
+    let w24_w27 = schedule_n(w08_w11, w12_w15, w16_w19, w20_w23);
+    let abcdew = compute_n::<1, N>(abcdew, w24_w27);
+    let w28_w31 = schedule_n(w12_w15, w16_w19, w20_w23, w24_w27);
+    let abcdew = compute_n::<1, N>(abcdew, w28_w31);
+    let w32_w35 = schedule_n(w16_w19, w20_w23, w24_w27, w28_w31);
+    let abcdew = compute_n::<1, N>(abcdew, w32_w35);
+    let w36_w39 = schedule_n(w20_w23, w24_w27, w28_w31, w32_w35);
+    let abcdew = compute_n::<1, N>(abcdew, w36_w39);
+    let w40_w43 = schedule_n(w24_w27, w28_w31, w32_w35, w36_w39);
+    let abcdew = compute_n::<1, N>(abcdew, w40_w43);
+
+    // 41-60
+    let w44_w47 = schedule_n(w28_w31, w32_w35, w36_w39, w40_w43);
+    let abcdew = compute_n::<2, N>(abcdew, w44_w47);
+    let w48_w51 = schedule_n(w32_w35, w36_w39, w40_w43, w44_w47);
+    let abcdew = compute_n::<2, N>(abcdew, w48_w51);
+    let w52_w55 = schedule_n(w36_w39, w40_w43, w44_w47, w48_w51);
+    let abcdew = compute_n::<2, N>(abcdew, w52_w55);
+    let w56_w59 = schedule_n(w40_w43, w44_w47, w48_w51, w52_w55);
+    let abcdew = compute_n::<2, N>(abcdew, w56_w59);
+    let w60_w63 = schedule_n(w44_w47, w48_w51, w52_w55, w56_w59);
+    let abcdew = compute_n::<2, N>(abcdew, w60_w63);
+
+    // 61-80
+    let w64_w67 = schedule_n(w48_w51, w52_w55, w56_w59, w60_w63);
+    let abcdew = compute_n::<3, N>(abcdew, w64_w67);
+    let w68_w71 = schedule_n(w52_w55, w56_w59, w60_w63, w64_w67);
+    let abcdew = compute_n::<3, N>(abcdew, w68_w71);
+    let w72_w75 = schedule_n(w56_w59, w60_w63, w64_w67, w68_w71);
+    let abcdew = compute_n::<3, N>(abcdew, w72_w75);
+    let w76_w79 = schedule_n(w60_w63, w64_w67, w68_w71, w72_w75);
+    let abcdew = compute_n::<3, N>(abcdew, w76_w79);
+    let abcde = compute_n::<3, N>(abcdew, hash_value.map(|[_, h4]| h4));
+
+    return std::array::from_fn(|i| [_mm_add_epi32(abcde[i][0], hash_value[i][0]), abcde[i][1]])
+}
+
+#[inline(always)]
+unsafe fn schedule_n<const N: usize>(w0_3: [__m128i; N], w4_7: [__m128i; N], w8_11: [__m128i; N], w12_15: [__m128i; N]) -> [__m128i; N] {
+    return std::array::from_fn(|i| _mm_sha1msg2_epu32(_mm_xor_si128(_mm_sha1msg1_epu32(w0_3[i], w4_7[i]), w8_11[i]), w12_15[i]))
+}
+
+#[inline(always)]
+unsafe fn compute_n<const FUNC: i32, const N: usize>(abcdew: [[__m128i; 2]; N], wx4: [__m128i; N]) -> [[__m128i; 2]; N] {
+    return std::array::from_fn(|i| compute::<FUNC>(abcdew[i], wx4[i]))
+}
+
 /// |if 0 <= t <= 15|
 /// |:-------------:|
 /// |    w0 = m0    |
@@ -112,10 +386,10 @@ unsafe fn hash_block(hash_value: [__m128i; 2], bytes: &[u8]) -> [__m128i; 2] {
 #[cfg(target_feature = "ssse3")]
 unsafe fn schedule_v0(bytes: &[u8]) -> [__m128i; 4] {
     let mask = _mm_set_epi64x(0x0001020304050607, 0x08090A0B0C0D0E0F);
-    let q0 = _mm_load_si128(bytes.as_ptr().cast());
-    let q1 = _mm_load_si128(bytes.as_ptr().add(16).cast());
-    let q2 = _mm_load_si128(bytes.as_ptr().add(32).cast());
-    let q3 = _mm_load_si128(bytes.as_ptr().add(48).cast());
+    let q0 = _mm_loadu_si128(bytes.as_ptr().cast());
+    let q1 = _mm_loadu_si128(bytes.as_ptr().add(16).cast());
+    let q2 = _mm_loadu_si128(bytes.as_ptr().add(32).cast());
+    let q3 = _mm_loadu_si128(bytes.as_ptr().add(48).cast());
     return [
         _mm_shuffle_epi8(q0, mask), 
         _mm_shuffle_epi8(q1, mask),
@@ -0,0 +1,174 @@
+/// Incremental SHA1 over a message fed in pieces.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::Sha1;
+///
+/// let mut hasher = Sha1::new();
+/// hasher.update("The quick brown fox ".as_bytes());
+/// hasher.update("jumps over the lazy dog".as_bytes());
+///
+/// assert_eq!(hasher.finalize(), simd_sha1::hash("The quick brown fox jumps over the lazy dog".as_bytes()));
+/// ```
+///
+/// Resuming from a chaining value, e.g. to append to a message whose digest is known:
+///
+/// ```
+/// use simd_sha1::Sha1;
+///
+/// let prefix = [0x61; 64];
+/// let mut state = simd_sha1::IV;
+/// simd_sha1::compress(&mut state, &[prefix]);
+///
+/// let mut hasher = Sha1::with_iv(state, 64);
+/// hasher.update("suffix".as_bytes());
+///
+/// assert_eq!(hasher.finalize(), simd_sha1::hash(&[&prefix[..], "suffix".as_bytes()].concat()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: [u8; 64],
+    length: u64,
+    /// Number of leading bits of `buffer[length % 64]` that belong to the message, after
+    /// [`update_bits`](Sha1::update_bits) with a partial last byte.
+    bits: u8,
+    compress: fn(&mut [u32; 5], &[[u8; 64]]),
+}
+
+impl Sha1 {
+    pub fn new() -> Sha1 {
+        return Sha1::with_iv(crate::IV, 0)
+    }
+
+    /// Starts from the chaining value `iv`, as if `processed_len` bytes had already been hashed.
+    ///
+    /// # Panics
+    ///
+    /// If `processed_len` is not a whole number of 64-byte blocks.
+    pub fn with_iv(iv: [u32; 5], processed_len: u64) -> Sha1 {
+        return Sha1::with_compress(iv, processed_len, crate::compress)
+    }
+
+    /// Runs the same padding and buffering over another compression function, for SHA-0.
+    pub(crate) fn with_compress(iv: [u32; 5], processed_len: u64, compress: fn(&mut [u32; 5], &[[u8; 64]])) -> Sha1 {
+        assert!(processed_len.is_multiple_of(64), "processed length must be a multiple of 64 bytes");
+        return Sha1 { state: iv, buffer: [0; 64], length: processed_len, bits: 0, compress }
+    }
+
+    /// Returns the chaining value after the last complete block. Bytes of an incomplete block
+    /// are still buffered and not part of it.
+    pub fn state(&self) -> [u32; 5] {
+        return self.state
+    }
+
+    /// Returns the number of bytes hashed so far, including the `processed_len` of
+    /// [`with_iv`](Sha1::with_iv).
+    pub fn processed_len(&self) -> u64 {
+        return self.length
+    }
+
+    /// # Panics
+    ///
+    /// After [`update_bits`](Sha1::update_bits) left a partial byte.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        assert!(self.bits == 0, "a partial byte must be the end of the message");
+        let buffered = (self.length % 64) as usize;
+        self.length += bytes.len() as u64;
+        if buffered > 0 {
+            let take = bytes.len().min(64 - buffered);
+            self.buffer[buffered..buffered + take].copy_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if buffered + take < 64 {
+                return
+            }
+            (self.compress)(&mut self.state, &[self.buffer]);
+        }
+
+        let (blocks, remainder) = bytes.as_chunks::<64>();
+        (self.compress)(&mut self.state, blocks);
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+    }
+
+    /// Feeds the first `bit_len` bits of `bytes`, most significant bit first. When `bit_len` is not a
+    /// multiple of 8, the high bits of the last byte are used and the message must end there.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is shorter than `bit_len` bits, or after a previous partial byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use simd_sha1::Sha1;
+    ///
+    /// let mut hasher = Sha1::new();
+    /// hasher.update("ab".as_bytes());
+    /// hasher.update_bits(&[0x63, 0xFF], 4);
+    ///
+    /// assert_eq!(hasher.finalize(), simd_sha1::hash_bits("abc".as_bytes(), 20));
+    /// ```
+    pub fn update_bits(&mut self, bytes: &[u8], bit_len: u64) {
+        assert!(bit_len <= bytes.len() as u64 * 8, "{bit_len} bits requested from {} bytes", bytes.len());
+        let whole = (bit_len / 8) as usize;
+        self.update(&bytes[..whole]);
+        let bits = (bit_len % 8) as u8;
+        if bits > 0 {
+            self.buffer[(self.length % 64) as usize] = bytes[whole] & !(0xFF >> bits);
+            self.bits = bits;
+        }
+    }
+
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_length = (self.length << 3) + self.bits as u64;
+        let buffered = (self.length % 64) as usize;
+        let mut tail = [0; 128];
+        tail[..buffered].copy_from_slice(&self.buffer[..buffered]);
+        tail[buffered] = match self.bits {
+            0 => 0x80,
+            bits => self.buffer[buffered] | 0x80 >> bits,
+        };
+        let tail_length = if buffered < 56 { 64 } else { 128 };
+        tail[tail_length - 8..tail_length].copy_from_slice(&bit_length.to_be_bytes());
+        let (blocks, _) = tail[..tail_length].as_chunks::<64>();
+        (self.compress)(&mut self.state, blocks);
+
+        let mut digest = [0; 20];
+        for (bytes, h) in digest.chunks_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&h.to_be_bytes());
+        }
+        return digest
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Sha1 {
+        return Sha1::new()
+    }
+}
+
+/// Hashes the first `bit_len` bits of `bytes`, most significant bit first, as in the bit-oriented
+/// SHAVS vectors.
+///
+/// # Panics
+///
+/// If `bytes` is shorter than `bit_len` bits.
+///
+/// # Examples
+///
+/// ```
+/// // the five bits 10011
+/// let expect = [
+///     0x29, 0x82, 0x6B, 0x00, 0x3B, 0x90, 0x6E, 0x66, 0x0E, 0xFF,
+///     0x40, 0x27, 0xCE, 0x98, 0xAF, 0x35, 0x31, 0xAC, 0x75, 0xBA
+/// ];
+///
+/// assert_eq!(simd_sha1::hash_bits(&[0x98], 5), expect);
+/// assert_eq!(simd_sha1::hash_bits("abc".as_bytes(), 24), simd_sha1::hash("abc".as_bytes()));
+/// ```
+pub fn hash_bits(bytes: &[u8], bit_len: u64) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update_bits(bytes, bit_len);
+    return hasher.finalize()
+}
@@ -0,0 +1,165 @@
+//! The TLS pseudorandom function with SHA1: `P_SHA1` (RFC 2246 section 5, RFC 5246 section 5).
+//!
+//! TLS 1.2 cipher suites configured with SHA1 use `P_SHA1` as the whole PRF. TLS 1.0 and 1.1 XOR
+//! `P_MD5` over the first half of the secret with `P_SHA1` over the second half; only the SHA1
+//! half is here, see [`tls10_sha1_half`].
+//!
+//! `P_SHA1` is a chain of HMACs under one secret. The secret is keyed once and every MAC starts
+//! from the cached midstates, see [`hmac::Key`](crate::hmac::Key).
+
+use crate::hmac::Key;
+
+pub const MASTER_SECRET_LABEL: &[u8] = b"master secret";
+pub const KEY_EXPANSION_LABEL: &[u8] = b"key expansion";
+
+/// `P_SHA1(secret, seed)`, filling `output`.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::tls_prf;
+///
+/// let mut output = [0; 40];
+/// tls_prf::p_sha1("secret".as_bytes(), "seed".as_bytes(), &mut output);
+///
+/// // OpenSSL's TLS1-PRF with SHA1
+/// assert_eq!(output, [
+///     0x5D, 0x55, 0x43, 0x2B, 0xDF, 0xE9, 0xB9, 0x3A, 0x81, 0x3A,
+///     0xCC, 0x93, 0x2A, 0xDF, 0x0F, 0x29, 0x78, 0x15, 0xD1, 0xC8,
+///     0x31, 0x66, 0xA9, 0x83, 0xEA, 0x04, 0x2D, 0x41, 0x06, 0x65,
+///     0xDF, 0xA1, 0x92, 0x68, 0xD9, 0x97, 0x74, 0xFD, 0x6F, 0x3F
+/// ]);
+/// ```
+pub fn p_sha1(secret: &[u8], seed: &[u8], output: &mut [u8]) {
+    p_hash(&Key::new(secret), &[seed], output);
+}
+
+/// The TLS 1.2 PRF with SHA1: `P_SHA1(secret, label || seed)`.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::tls_prf;
+///
+/// let mut output = [0; 100];
+/// tls_prf::prf(&[0x9B; 48], "test label".as_bytes(), &[0xA0; 64], &mut output);
+///
+/// assert_eq!(output[..20], [
+///     0xF7, 0x3B, 0x93, 0xE4, 0x85, 0xED, 0x2C, 0x96, 0x45, 0xD3,
+///     0x28, 0xBB, 0xB5, 0x10, 0x96, 0x57, 0x89, 0xF5, 0xE3, 0xAF
+/// ]);
+/// ```
+pub fn prf(secret: &[u8], label: &[u8], seed: &[u8], output: &mut [u8]) {
+    p_hash(&Key::new(secret), &[label, seed], output);
+}
+
+/// The `P_SHA1` half of the TLS 1.0 and 1.1 PRF: `P_SHA1(S2, label || seed)`, where `S2` is the
+/// second half of `secret`, sharing its first byte with the first half if the length is odd. The
+/// PRF output is this XOR `P_MD5(S1, label || seed)`.
+///
+/// # Examples
+///
+/// The published TLS 1.0 PRF test vector, with the `P_MD5` half precomputed:
+///
+/// ```
+/// use simd_sha1::tls_prf;
+///
+/// // P_MD5([0xAB; 24], "PRF Testvector" || [0xCD; 64])
+/// let p_md5 = [
+///     0x50, 0x02, 0x94, 0x34, 0x1C, 0x32, 0xC1, 0x32, 0xEF, 0xCC, 0x69, 0x26, 0x55, 0x7B, 0x98, 0x86,
+///     0x94, 0xF5, 0x1D, 0x87, 0x31, 0x1C, 0x0D, 0xBC, 0xCA, 0x30, 0x29, 0x23, 0xC7, 0x48, 0x1B, 0x60,
+///     0xE2, 0x67, 0x8A, 0xB9, 0x24, 0xA9, 0x74, 0xDD, 0xD2, 0xDB, 0x6F, 0xD4, 0x6D, 0x80, 0x49, 0x23,
+///     0x1B, 0x66, 0xA3, 0x0E, 0x6A, 0x5F, 0xD5, 0xE3, 0x77, 0x24, 0x09, 0xBB, 0xAD, 0x50, 0xDC, 0xD5,
+///     0x3B, 0xE7, 0x47, 0xD0, 0xCA, 0xFC, 0xC6, 0x3D, 0x17, 0x9D, 0x81, 0x64, 0x2B, 0xBC, 0x63, 0x7A,
+///     0xA1, 0x24, 0x54, 0x3D, 0x77, 0x2F, 0xB5, 0x78, 0x05, 0x42, 0xCE, 0x27, 0x0B, 0x45, 0x60, 0xD3,
+///     0x94, 0xAF, 0x71, 0x73, 0xB0, 0x25, 0x4B, 0xBC
+/// ];
+/// let mut output = [0; 104];
+/// tls_prf::tls10_sha1_half(&[0xAB; 48], "PRF Testvector".as_bytes(), &[0xCD; 64], &mut output);
+/// for (byte, md5) in output.iter_mut().zip(p_md5) {
+///     *byte ^= md5;
+/// }
+///
+/// assert_eq!(output, [
+///     0xD3, 0xD4, 0xD1, 0xE3, 0x49, 0xB5, 0xD5, 0x15, 0x04, 0x46, 0x66, 0xD5, 0x1D, 0xE3, 0x2B, 0xAB,
+///     0x25, 0x8C, 0xB5, 0x21, 0xB6, 0xB0, 0x53, 0x46, 0x3E, 0x35, 0x48, 0x32, 0xFD, 0x97, 0x67, 0x54,
+///     0x44, 0x3B, 0xCF, 0x9A, 0x29, 0x65, 0x19, 0xBC, 0x28, 0x9A, 0xBC, 0xBC, 0x11, 0x87, 0xE4, 0xEB,
+///     0xD3, 0x1E, 0x60, 0x23, 0x53, 0x77, 0x6C, 0x40, 0x8A, 0xAF, 0xB7, 0x4C, 0xBC, 0x85, 0xEF, 0xF6,
+///     0x92, 0x55, 0xF9, 0x78, 0x8F, 0xAA, 0x18, 0x4C, 0xBB, 0x95, 0x7A, 0x98, 0x19, 0xD8, 0x4A, 0x5D,
+///     0x7E, 0xB0, 0x06, 0xEB, 0x45, 0x9D, 0x3A, 0xE8, 0xDE, 0x98, 0x10, 0x45, 0x4B, 0x8B, 0x2D, 0x8F,
+///     0x1A, 0xFB, 0xC6, 0x55, 0xA8, 0xC9, 0xA0, 0x13
+/// ]);
+/// ```
+pub fn tls10_sha1_half(secret: &[u8], label: &[u8], seed: &[u8], output: &mut [u8]) {
+    let s2 = &secret[secret.len() / 2..];
+    p_hash(&Key::new(s2), &[label, seed], output);
+}
+
+/// The 48-byte master secret of a TLS 1.2 session with the SHA1 PRF.
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::tls_prf;
+///
+/// let master_secret = tls_prf::master_secret(&[0x03; 48], &[0x11; 32], &[0x22; 32]);
+///
+/// // OpenSSL's TLS1-PRF with SHA1
+/// assert_eq!(master_secret, [
+///     0x50, 0xB0, 0x8F, 0x25, 0x48, 0x82, 0x2D, 0xA7, 0xA6, 0xED, 0x13, 0x8B, 0x78, 0x40, 0x6D, 0x1A,
+///     0x65, 0x99, 0x40, 0xE5, 0xCF, 0x70, 0x0A, 0xD6, 0x87, 0x88, 0x5A, 0xA1, 0xEB, 0x31, 0x6E, 0x5D,
+///     0xBE, 0x6D, 0x3F, 0x47, 0xA4, 0xF8, 0xD9, 0x83, 0xA2, 0xE7, 0x10, 0xBB, 0x20, 0x7C, 0x42, 0xB1
+/// ]);
+/// ```
+pub fn master_secret(pre_master_secret: &[u8], client_random: &[u8; 32], server_random: &[u8; 32]) -> [u8; 48] {
+    let mut master_secret = [0; 48];
+    p_hash(&Key::new(pre_master_secret), &[MASTER_SECRET_LABEL, client_random, server_random], &mut master_secret);
+    return master_secret
+}
+
+/// Fills `key_block` for a TLS 1.2 session with the SHA1 PRF. Note the randoms are in the other
+/// order than for [`master_secret`].
+///
+/// # Examples
+///
+/// ```
+/// use simd_sha1::tls_prf;
+///
+/// let master_secret = tls_prf::master_secret(&[0x03; 48], &[0x11; 32], &[0x22; 32]);
+/// // two HMAC-SHA1 keys and two AES-128 keys
+/// let mut key_block = [0; 2 * 20 + 2 * 16];
+///
+/// tls_prf::key_block(&master_secret, &[0x22; 32], &[0x11; 32], &mut key_block);
+///
+/// // OpenSSL's TLS1-PRF with SHA1
+/// assert_eq!(key_block, [
+///     0x07, 0x2B, 0xAE, 0x8F, 0x88, 0x0D, 0x78, 0x77, 0x86, 0xF5, 0xB0, 0xCF, 0x4F, 0x84, 0x93, 0x46,
+///     0x8E, 0xD5, 0xAD, 0x79, 0xD5, 0xDD, 0xA8, 0x13, 0x36, 0xA1, 0x86, 0x76, 0x71, 0x78, 0x83, 0xEF,
+///     0x30, 0xB7, 0x0D, 0x7D, 0xFD, 0x67, 0xD0, 0x85, 0x55, 0xAF, 0xD4, 0x06, 0x1B, 0x1E, 0xA5, 0x32,
+///     0xD7, 0xFE, 0x24, 0x0D, 0x60, 0x67, 0x99, 0x00, 0x84, 0x82, 0x0D, 0x89, 0xFE, 0xD3, 0x11, 0x09,
+///     0x96, 0x17, 0x7A, 0x8D, 0x40, 0x64, 0x1E, 0x5B
+/// ]);
+/// ```
+pub fn key_block(master_secret: &[u8; 48], server_random: &[u8; 32], client_random: &[u8; 32], key_block: &mut [u8]) {
+    p_hash(&Key::new(master_secret), &[KEY_EXPANSION_LABEL, server_random, client_random], key_block);
+}
+
+/// `P_SHA1` under `key` with the concatenation of `seed` as the seed.
+fn p_hash(key: &Key, seed: &[&[u8]], output: &mut [u8]) {
+    // A(1) = HMAC(secret, seed)
+    let mut hasher = key.hasher();
+    for part in seed {
+        hasher.update(part);
+    }
+    let mut a = hasher.finalize();
+
+    for chunk in output.chunks_mut(20) {
+        let mut hasher = key.hasher();
+        hasher.update(&a);
+        for part in seed {
+            hasher.update(part);
+        }
+        chunk.copy_from_slice(&hasher.finalize()[..chunk.len()]);
+        a = key.mac(&a);
+    }
+}